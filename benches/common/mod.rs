@@ -0,0 +1,55 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+// Input of the `fifty_mb` example, within the repo.
+const FIFTY_MB_PATH: &str = "las_files/big.las";
+const SIZE_IN_MB: usize = 50;
+
+/// The .las file the `fifty_mb` example parses, or `LIBLAS_BENCH_FILE` when it is set. When neither exists, one
+/// of the same size is generated (once) from the rows of a sample file.
+pub fn fifty_mb_las() -> io::Result<PathBuf> {
+    let path = env::var_os("LIBLAS_BENCH_FILE").map_or_else(|| PathBuf::from(FIFTY_MB_PATH), PathBuf::from);
+    if path.exists() {
+        return Ok(path);
+    }
+    let generated = env::temp_dir().join("liblas_bench_fifty_mb.las");
+    if !generated.exists() {
+        println!(
+            "'{}' doesn't exist, generating '{}'",
+            path.display(),
+            generated.display()
+        );
+        generate_las(&generated)?;
+    }
+    Ok(generated)
+}
+
+// Every line of `las_files/_good_sample_1.las` up to and including "~A", to put generated rows after.
+fn sample_header() -> io::Result<String> {
+    let sample = fs::read_to_string("las_files/_good_sample_1.las")?;
+    // "~A" also appears in a comment, so look for it at the start of a line.
+    let header_end = sample.find("\n~A").unwrap_or_default() + 1;
+    let header_end = header_end + sample[header_end..].find('\n').unwrap_or_default() + 1;
+    Ok(sample[..header_end].to_string())
+}
+
+fn generate_las(path: &PathBuf) -> io::Result<()> {
+    let header = sample_header()?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(header.as_bytes())?;
+    let (mut depth, mut size) = (0.0, header.len());
+    while size < SIZE_IN_MB * 1024 * 1024 {
+        let row = format!(
+            "{depth:10.3} {:8.3} 2.500 0.300 -999.250 10.000 11.000 12.000\n",
+            depth / 10.0
+        );
+        writer.write_all(row.as_bytes())?;
+        size += row.len();
+        depth += 0.5;
+    }
+    writer.flush()
+}
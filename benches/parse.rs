@@ -0,0 +1,28 @@
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::{io, time::Duration};
+
+mod common;
+
+//
+// Parses the same 50mb las file as the `fifty_mb` example, sequentially and on every core.
+//
+// `cargo bench --features parallel`
+// `LIBLAS_BENCH_FILE=/some/big.las cargo bench --features parallel`
+//
+
+fn parse_fifty_mb(c: &mut Criterion) {
+    let path = common::fifty_mb_las().unwrap();
+    let path = path.to_str().unwrap();
+
+    let mut group = c.benchmark_group("fifty_mb");
+    group.sample_size(10).measurement_time(Duration::from_secs(20));
+    group.bench_function("parse_into json", |b| {
+        b.iter(|| liblas::parse_into(path, io::sink(), liblas::OutputFormat::JSON).unwrap())
+    });
+    group.bench_function("parse", |b| b.iter(|| liblas::parse(path).unwrap()));
+    group.bench_function("parse_parallel", |b| b.iter(|| liblas::parse_parallel(path).unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, parse_fifty_mb);
+criterion_main!(benches);
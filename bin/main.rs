@@ -1,82 +1,763 @@
-use clap::Parser;
-use liblas::OutputFormat;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use liblas::{
+    BatchOptions, CsvOptions, CsvSink, DataLine, Diagnostic, Encoding, FilterOptions, FilterSink, JsonOptions,
+    JsonSink, LasFile, LasRun, LasWriter, LasWriterOptions, LineEnding, MergeOptions, MergePriority, NdjsonSink,
+    OutputFormat, ParseError, ParseOptions, ResampleMethod, Severity, Sink, Strictness, YamlSink,
+    sections::{VersionInformation, WellInformation},
+};
 use std::{
-    fs::{OpenOptions, create_dir_all},
-    path::PathBuf,
+    fs::{self, File, OpenOptions, create_dir_all},
+    io::{self, BufWriter, Cursor, Read, Write},
+    path::{Path, PathBuf},
     process::exit,
 };
 
+// Exit codes. Clap also uses 2 for bad arguments.
+const EXIT_INVALID: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
-struct Args {
-    /// Relative to binary location.
-    /// Path to .las file.
-    #[arg(short, long, required = true)]
-    las: String,
-
-    /// Relative to binary location.
-    /// Output path with file name ending in .json, .yml, or .yaml.
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a summary of the well header and curves.
+    Info(InfoArgs),
+    /// Run every check on a .las file. Exits with 0 when it is valid, 1 when it isn't and 2 when it can't be read.
+    Validate(ValidateArgs),
+    /// Convert between LAS, JSON, NDJSON, YAML, CSV and TSV.
+    Convert(ConvertArgs),
+    /// Rewrite a .las file in normalized form, with data lines and columns of ASCII log data aligned.
+    Fmt(FmtArgs),
+    /// Convert every .las file in directories or glob patterns into a mirrored tree, a few files at a time.
+    /// Prints a JSON report of every file. Exits with 1 when any of them failed.
+    Batch(BatchArgs),
+}
+
+#[derive(Args, Debug)]
+struct InfoArgs {
+    /// Path to .las file, or '-' for stdin.
+    input: String,
+
+    /// Stop at '~A' instead of reading ASCII log data, for cataloguing large archives quickly.
+    /// Rows are counted without parsing them, and curve ranges aren't shown.
+    #[arg(long)]
+    header_only: bool,
+
+    /// Text encoding of INPUT. 'auto' reads UTF-8, or Windows-1252 for files that aren't valid UTF-8.
+    #[arg(long, default_value = "auto")]
+    encoding: Encoding,
+}
+
+#[derive(Args, Debug)]
+struct ValidateArgs {
+    /// Path to .las file, or '-' for stdin.
+    input: String,
+
+    /// Text encoding of INPUT. 'auto' reads UTF-8, or Windows-1252 for files that aren't valid UTF-8.
+    #[arg(long, default_value = "auto")]
+    encoding: Encoding,
+}
+
+#[derive(Args, Debug)]
+struct ConvertArgs {
+    /// Path to .las file, or to a .json, .yaml or .yml export of one to convert back to LAS. '-' for stdin.
+    input: String,
+
+    /// Output path with file name ending in .json, .ndjson, .jsonl, .yml, .yaml, .csv, .tsv or .las. '-' for stdout.
     /// Only new files will be automatically created!
     /// If the path contains non-existent directories, you will need to use the '--force' switch.
-    #[arg(short, long, required = true)]
-    out: String,
+    output: String,
 
-    #[arg(short = 't', long, required = true)]
-    out_type: OutputFormat,
+    /// Output format. Worked out from the extension of OUTPUT when not given, required for stdout.
+    #[arg(short = 't', long)]
+    to: Option<OutputFormat>,
 
-    /// Will create directories within '--out' path if they do not exist.
+    /// Input format. Worked out from the extension of INPUT when not given, stdin is LAS unless told otherwise.
+    #[arg(long)]
+    from: Option<InputFormat>,
+
+    /// Will create directories within OUTPUT if they do not exist.
     /// If file already exists we will overwrite it.
     #[arg(short, long)]
     force: bool,
+
+    /// CSV/TSV only.
+    /// Write NULL values as empty cells.
+    #[arg(long)]
+    null_as_empty: bool,
+
+    /// CSV/TSV only.
+    /// Write the version and well information above the header row, as lines starting with '#'.
+    #[arg(long)]
+    preamble: bool,
+
+    /// JSON only.
+    /// Write ASCII log data as one array per curve, '"columns":{"GR":[...]}', instead of one array per row.
+    #[arg(long)]
+    columnar: bool,
+
+    /// Convert the depth (index) curve, along with STRT, STOP and STEP, to this unit before exporting.
+    /// eg. 'ft' or 'm'
+    #[arg(long)]
+    depth_unit: Option<String>,
+
+    /// Resample onto this step (in depth units, after '--depth-unit') before exporting.
+    /// STRT, STOP and STEP are updated to match.
+    #[arg(long)]
+    step: Option<f64>,
+
+    /// How to resample when using '--step'.
+    #[arg(long, default_value = "linear")]
+    resample_method: ResampleMethod,
+
+    /// Other runs of the same well to merge with INPUT before exporting, eg. a repeat pass or a deeper section.
+    /// Headers come from INPUT.
+    #[arg(long, num_args = 1..)]
+    merge: Vec<String>,
+
+    /// Which run to keep where merged runs overlap, in the order given (INPUT first).
+    #[arg(long, default_value = "first")]
+    merge_priority: MergePriority,
+
+    /// Merge runs even when their UWI, API, depth unit or curve units disagree, with a warning instead of an error.
+    #[arg(long)]
+    allow_mismatched_runs: bool,
+
+    /// Only export ASCII log data at or below this depth (in depth units, after '--depth-unit').
+    #[arg(long)]
+    top: Option<f64>,
+
+    /// Only export ASCII log data at or above this depth (in depth units, after '--depth-unit').
+    #[arg(long)]
+    base: Option<f64>,
+
+    /// Only export these curves, eg. 'GR,RHOB,NPHI'. The depth (index) curve is always exported.
+    #[arg(long, value_delimiter = ',')]
+    curves: Option<Vec<String>>,
+
+    /// Text encoding of INPUT. 'auto' reads UTF-8, or Windows-1252 for files that aren't valid UTF-8.
+    #[arg(long, default_value = "auto")]
+    encoding: Encoding,
+
+    /// Text encoding of LAS output. The encoding of INPUT by default, other formats are always UTF-8.
+    #[arg(long)]
+    output_encoding: Option<Encoding>,
 }
 
-fn create_file_path(path: PathBuf) {
-    let mut p = path;
-    p.pop();
-    let _ = create_dir_all(&p);
+#[derive(Args, Debug)]
+struct FmtArgs {
+    /// Path to .las file, or '-' for stdin.
+    input: String,
+
+    /// Where to write the formatted file, '-' for stdout.
+    #[arg(default_value = "-")]
+    output: String,
+
+    /// Overwrite INPUT instead of writing to OUTPUT. INPUT is only replaced once the formatted file has been
+    /// written in full.
+    #[arg(short, long, conflicts_with = "output")]
+    in_place: bool,
+
+    /// Will create directories within OUTPUT if they do not exist.
+    /// If file already exists we will overwrite it.
+    #[arg(short, long)]
+    force: bool,
+
+    /// Rewrite numeric ASCII log data with this many decimal places.
+    #[arg(long)]
+    decimals: Option<usize>,
+
+    /// Write section headers in upper case, eg. '~WELL INFORMATION'.
+    #[arg(long)]
+    uppercase_headers: bool,
+
+    /// Use '\r\n' line endings.
+    #[arg(long)]
+    crlf: bool,
+
+    /// Text encoding of INPUT. 'auto' reads UTF-8, or Windows-1252 for files that aren't valid UTF-8.
+    #[arg(long, default_value = "auto")]
+    encoding: Encoding,
+
+    /// Text encoding of the formatted file. The encoding of INPUT by default.
+    #[arg(long)]
+    output_encoding: Option<Encoding>,
 }
 
-fn main() {
-    let args = Args::parse();
+#[derive(Args, Debug)]
+struct BatchArgs {
+    /// Files, directories (searched recursively) or glob patterns, eg. 'archive/**/*.las'.
+    /// Quote glob patterns so the shell leaves them alone.
+    #[arg(required = true)]
+    inputs: Vec<String>,
+
+    /// Directory to write converted files to, in the same tree they were found in.
+    #[arg(short, long)]
+    out_dir: String,
+
+    /// Output format.
+    #[arg(short = 't', long)]
+    to: OutputFormat,
+
+    /// Number of files converted at once. Defaults to the number of CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Overwrite files that already exist in OUT_DIR.
+    #[arg(short, long)]
+    force: bool,
+
+    /// Where to write the JSON report, '-' for stdout.
+    #[arg(long, default_value = "-")]
+    report: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    Las,
+    /// A JSON export of a .las file
+    Json,
+    /// A YAML export of a .las file
+    Yaml,
+}
+
+// A failed command : what to tell the user, and the exit code.
+struct Failure {
+    message: String,
+    code: i32,
+}
+
+impl Failure {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            code: EXIT_ERROR,
+        }
+    }
 
-    // In `--las` if we were given a path that doesn't end in `.las`
-    if !args.las.ends_with(".las") {
-        println!("Error : '--las' path '{}' must be to a .las file!", args.las);
-        exit(1);
+    fn invalid(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            code: EXIT_INVALID,
+        }
     }
-    // If JSON export type but out path doesn't end in json
-    if args.out_type == OutputFormat::JSON && !args.out.ends_with(".json") {
-        println!("Error : '--out' path '{}' must be to a .json file!", args.out);
-        exit(1);
+}
+
+impl From<ParseError> for Failure {
+    fn from(e: ParseError) -> Self {
+        match e {
+            // Only ever asked for, eg. with '--curves'.
+            ParseError::Io(_) | ParseError::MissingCurve { .. } => Failure::new(e.to_string()),
+            _ => Failure::invalid(e.to_string()),
+        }
+    }
+}
+
+// A ParseError caused by an argument rather than the file, eg. '--depth-unit furlong'. Exits with EXIT_ERROR,
+// EXIT_INVALID is only for invalid files.
+fn argument_error(e: ParseError) -> Failure {
+    match e {
+        ParseError::Io(_) => e.into(),
+        _ => Failure::new(e.to_string()),
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(e: io::Error) -> Self {
+        Failure::new(e.to_string())
+    }
+}
+
+fn is_stdio(path: &str) -> bool {
+    path == "-"
+}
+
+fn extension(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default()
+}
+
+fn open_input(path: &str) -> Result<Box<dyn Read>, Failure> {
+    if is_stdio(path) {
+        return Ok(Box::new(io::stdin().lock()));
     }
-    // If YAML or YML export type but path doesn't end in YAML or YML
-    if (args.out_type == OutputFormat::YAML || args.out_type == OutputFormat::YML)
-        && (!args.out.ends_with(".yaml") && !args.out.ends_with(".yml"))
-    {
-        println!("Error : '--out' path '{}' must be to a .yaml or .yml file!", args.out);
-        exit(1);
+    let file = File::open(path).map_err(|e| Failure::new(format!("unable to open '{path}': {e}")))?;
+    Ok(Box::new(file))
+}
+
+fn is_same_file(a: &str, b: &str) -> bool {
+    if is_stdio(a) || is_stdio(b) {
+        return false;
+    }
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
     }
+}
 
+fn open_output(path: &str, force: bool) -> Result<Box<dyn Write>, Failure> {
+    if is_stdio(path) {
+        return Ok(Box::new(BufWriter::new(io::stdout().lock())));
+    }
     let mut file_options = OpenOptions::new();
     file_options.write(true);
-
-    if args.force {
-        create_file_path(args.out.clone().into());
+    if force {
+        create_file_path(path.into());
         file_options.truncate(true); // Truncate the file if it exists, overwriting its contents
         file_options.create(true);
     } else {
         file_options.create_new(true); // Only create if it is a new file
     }
+    let file = file_options.open(path).map_err(|e| {
+        Failure::new(format!(
+            "unable to create '{path}': {e}\nYou may need to use the '--force' switch to create non-existent directories or overwrite an existing file"
+        ))
+    })?;
+    Ok(Box::new(file))
+}
+
+fn create_file_path(path: PathBuf) {
+    let mut p = path;
+    p.pop();
+    let _ = create_dir_all(&p);
+}
+
+fn input_format(args: &ConvertArgs) -> Result<InputFormat, Failure> {
+    if let Some(from) = args.from {
+        return Ok(from);
+    }
+    if is_stdio(&args.input) {
+        return Ok(InputFormat::Las);
+    }
+    match extension(&args.input).as_str() {
+        "las" => Ok(InputFormat::Las),
+        "json" => Ok(InputFormat::Json),
+        "yaml" | "yml" => Ok(InputFormat::Yaml),
+        _ => Err(Failure::new(format!(
+            "'{}' isn't a .las, .json, .yaml or .yml file, use '--from' to say what it is",
+            args.input
+        ))),
+    }
+}
+
+fn output_format(args: &ConvertArgs) -> Result<OutputFormat, Failure> {
+    if let Some(to) = args.to.as_ref() {
+        return Ok(to.clone());
+    }
+    match extension(&args.output).as_str() {
+        "json" => Ok(OutputFormat::JSON),
+        "ndjson" | "jsonl" => Ok(OutputFormat::NDJSON),
+        "yaml" => Ok(OutputFormat::YAML),
+        "yml" => Ok(OutputFormat::YML),
+        "csv" => Ok(OutputFormat::CSV),
+        "tsv" => Ok(OutputFormat::TSV),
+        "las" => Ok(OutputFormat::LAS),
+        _ => Err(Failure::new(format!(
+            "unable to tell which format to write '{}' in, use '--to'",
+            args.output
+        ))),
+    }
+}
+
+// Parses INPUT, or stdin, into a LasFile.
+fn read_las_file(path: &str, options: ParseOptions) -> Result<(LasFile, Vec<Diagnostic>), Failure> {
+    Ok(liblas::parse_from_with_options(open_input(path)?, options)?)
+}
+
+fn parse_options(encoding: Encoding) -> ParseOptions {
+    ParseOptions {
+        encoding,
+        ..Default::default()
+    }
+}
+
+fn print_diagnostics(diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics.iter() {
+        let severity = match diagnostic.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match diagnostic.line_number {
+            Some(line_number) => eprintln!("{severity} (line {line_number}): {}", diagnostic.rule),
+            None => eprintln!("{severity}: {}", diagnostic.rule),
+        }
+    }
+}
+
+fn info(args: &InfoArgs) -> Result<(), Failure> {
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        encoding: args.encoding,
+        ..Default::default()
+    };
+    if args.header_only {
+        if is_stdio(&args.input) {
+            return Err(Failure::new("'--header-only' needs a file, not stdin"));
+        }
+        let (header, diagnostics) = liblas::parse_header_with_options(&args.input, options)?;
+        let rows = header.count_rows(File::open(&args.input)?)?;
+        let summary = Summary {
+            version: &header.version_information,
+            well: &header.well_information,
+            curves: &header.curve_information.curves,
+            rows,
+            ranges: vec![],
+        };
+        return print_summary(&summary, &diagnostics);
+    }
+
+    let (las_file, diagnostics) = read_las_file(&args.input, options)?;
+    let ranges = (0..las_file.curve_information.curves.len())
+        .map(|i| {
+            las_file
+                .ascii_log_data
+                .curves
+                .get(i)
+                .and_then(|c| {
+                    let values: Vec<f64> = c.iter().flatten().collect();
+                    let min = values.iter().copied().reduce(f64::min)?;
+                    let max = values.iter().copied().reduce(f64::max)?;
+                    Some(format!("{min} to {max}"))
+                })
+                .unwrap_or_else(|| "no values".to_string())
+        })
+        .collect();
+    let summary = Summary {
+        version: &las_file.version_information,
+        well: &las_file.well_information,
+        curves: &las_file.curve_information.curves,
+        rows: Some(las_file.ascii_log_data.num_rows()),
+        ranges,
+    };
+    print_summary(&summary, &diagnostics)
+}
+
+// What `info` prints, from a LasFile or only its header.
+struct Summary<'a> {
+    version: &'a VersionInformation,
+    well: &'a WellInformation,
+    curves: &'a [DataLine],
+    rows: Option<usize>,
+    // Range of values of each curve, empty when log data wasn't read.
+    ranges: Vec<String>,
+}
+
+fn print_summary(summary: &Summary, diagnostics: &[Diagnostic]) -> Result<(), Failure> {
+    let (version, well) = (summary.version, summary.well);
+    let text = |line: Option<&DataLine>| {
+        line.and_then(|l| l.value.as_ref())
+            .map(|v| v.to_string())
+            .filter(|v| !v.trim().is_empty())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let depth_unit = summary.curves.first().and_then(|c| c.unit.clone()).unwrap_or_default();
+
+    println!(
+        "Version : {} (wrapped: {})",
+        text(Some(&version.version)),
+        text(Some(&version.wrap))
+    );
+    println!("Well    : {}", text(well.well.as_ref()));
+    println!("Company : {}", text(well.comp.as_ref()));
+    println!("Field   : {}", text(well.fld.as_ref()));
+    println!("UWI/API : {} / {}", text(well.uwi.as_ref()), text(well.api.as_ref()));
+    println!(
+        "Depth   : {} to {} {depth_unit}, step {}",
+        text(Some(&well.strt)),
+        text(Some(&well.stop)),
+        text(Some(&well.step))
+    );
+    println!("NULL    : {}", text(Some(&well.null)));
+    match summary.rows {
+        Some(rows) => println!("Rows    : {rows}"),
+        None => println!("Rows    : -"),
+    }
+    println!("Curves  : {}", summary.curves.len());
+
+    let mnemonic_width = summary
+        .curves
+        .iter()
+        .map(|c| c.mnemonic.len())
+        .max()
+        .unwrap_or_default();
+    for (i, curve) in summary.curves.iter().enumerate() {
+        let unit = curve.unit.as_deref().unwrap_or_default();
+        let description = curve.description.as_deref().unwrap_or_default();
+        match summary.ranges.get(i) {
+            Some(range) => println!(
+                "  {:<mnemonic_width$} {unit:<8} {range:<24} {description}",
+                curve.mnemonic
+            ),
+            None => println!("  {:<mnemonic_width$} {unit:<8} {description}", curve.mnemonic),
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        eprintln!(
+            "{} problem(s) found, run 'liblas validate' for details",
+            diagnostics.len()
+        );
+    }
+    Ok(())
+}
+
+fn validate(args: &ValidateArgs) -> Result<(), Failure> {
+    let options = ParseOptions {
+        strictness: Strictness::Lenient,
+        validate_data: true,
+        encoding: args.encoding,
+        ..Default::default()
+    };
+    let (_, diagnostics) = read_las_file(&args.input, options)?;
+    print_diagnostics(&diagnostics);
+    if !diagnostics.is_empty() {
+        return Err(Failure::invalid(format!("{} problem(s) found", diagnostics.len())));
+    }
+    println!("'{}' is valid", args.input);
+    Ok(())
+}
+
+// INPUT merged with every '--merge' run.
+fn merge_runs(args: &ConvertArgs) -> Result<LasFile, Failure> {
+    let (first, _) = read_las_file(&args.input, parse_options(args.encoding))?;
+    let name = match is_stdio(&args.input) {
+        true => "stdin".to_string(),
+        false => Path::new(&args.input)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| args.input.clone()),
+    };
+    let mut runs = vec![LasRun::new(&name, first)];
+    for path in args.merge.iter() {
+        runs.push(LasRun::open(path)?);
+    }
+    let options = MergeOptions {
+        priority: args.merge_priority,
+        strictness: match args.allow_mismatched_runs {
+            true => Strictness::Lenient,
+            false => Strictness::Strict,
+        },
+    };
+    let (las_file, diagnostics) = LasFile::merge(runs, &options)?;
+    print_diagnostics(&diagnostics);
+    Ok(las_file)
+}
+
+// INPUT as is, or converted when '--merge', '--depth-unit' or '--step' are used.
+fn open_source(args: &ConvertArgs) -> Result<Box<dyn Read>, Failure> {
+    if args.merge.is_empty() && args.depth_unit.is_none() && args.step.is_none() {
+        return open_input(&args.input);
+    }
+    let mut las_file = match args.merge.is_empty() {
+        true => read_las_file(&args.input, parse_options(args.encoding))?.0,
+        false => merge_runs(args)?,
+    };
+    if let Some(depth_unit) = args.depth_unit.as_ref() {
+        las_file.convert_depth_units(depth_unit).map_err(argument_error)?;
+    }
+    if let Some(step) = args.step {
+        las_file.resample(step, args.resample_method).map_err(argument_error)?;
+    }
+    // Written in the encoding of INPUT, so LAS output is too.
+    let mut writer = LasWriter::new(vec![], LasWriterOptions::default());
+    writer.write(&las_file)?;
+    Ok(Box::new(Cursor::new(writer.into_inner()?)))
+}
+
+// A .json, .yaml or .yml export back to .las, streamed straight into `output`.
+fn convert_export(args: &ConvertArgs, from: InputFormat, to: &OutputFormat) -> Result<(), Failure> {
+    if *to != OutputFormat::LAS {
+        return Err(Failure::new("JSON and YAML exports can only be converted back to LAS"));
+    }
+    let converts = args.depth_unit.is_some() || args.step.is_some() || !args.merge.is_empty();
+    let filters = args.top.is_some() || args.base.is_some() || args.curves.is_some();
+    if converts || filters {
+        return Err(Failure::new(
+            "JSON and YAML exports can only be converted back to LAS as they are",
+        ));
+    }
+    let input = open_input(&args.input)?;
+    let options = LasWriterOptions {
+        encoding: args.output_encoding.unwrap_or_default(),
+        ..Default::default()
+    };
+    let mut writer = LasWriter::new(open_output(&args.output, args.force)?, options);
+    match from {
+        InputFormat::Json => writer.write_json(input)?,
+        _ => writer.write_yaml(input)?,
+    }
+    Ok(())
+}
+
+// Streams INPUT into `sink`, without building a LasFile.
+fn stream<S: Sink>(source: Box<dyn Read>, sink: &mut S, args: &ConvertArgs) -> Result<(), Failure> {
+    liblas::stream_with_options(source, sink, parse_options(args.encoding))?;
+    Ok(())
+}
+
+// LAS is written from a LasFile, so '--top', '--base' and '--curves' are applied to that.
+fn write_las(
+    source: Box<dyn Read>,
+    output: Box<dyn Write>,
+    filter: &FilterOptions,
+    args: &ConvertArgs,
+) -> Result<(), Failure> {
+    let (mut las_file, _) = liblas::parse_from_with_options(source, parse_options(args.encoding))?;
+    if filter.top.is_some() || filter.base.is_some() {
+        let top = filter.top.unwrap_or(f64::NEG_INFINITY);
+        let base = filter.base.unwrap_or(f64::INFINITY);
+        las_file.slice_depth(top, base).map_err(argument_error)?;
+    }
+    if let Some(curves) = filter.curves.as_ref() {
+        las_file
+            .select_curves(&curves.iter().map(String::as_str).collect::<Vec<_>>())
+            .map_err(argument_error)?;
+    }
+    let options = LasWriterOptions {
+        encoding: args.output_encoding.unwrap_or_default(),
+        ..Default::default()
+    };
+    LasWriter::new(output, options).write(&las_file)?;
+    Ok(())
+}
+
+fn convert(args: &ConvertArgs) -> Result<(), Failure> {
+    let from = input_format(args)?;
+    let to = output_format(args)?;
+    // Opening OUTPUT truncates it before INPUT is read.
+    if is_same_file(&args.input, &args.output) {
+        return Err(Failure::new(format!(
+            "OUTPUT '{}' is INPUT, use 'liblas fmt --in-place' to rewrite a file",
+            args.output
+        )));
+    }
+    if from != InputFormat::Las {
+        convert_export(args, from, &to)?;
+    } else {
+        let source = open_source(args)?;
+        let output = open_output(&args.output, args.force)?;
+        let filter = FilterOptions {
+            top: args.top,
+            base: args.base,
+            curves: args.curves.clone(),
+        };
+        match to {
+            OutputFormat::CSV | OutputFormat::TSV => {
+                let options = CsvOptions {
+                    null_as_empty: args.null_as_empty,
+                    preamble: args.preamble,
+                    ..if to == OutputFormat::TSV {
+                        CsvOptions::tsv()
+                    } else {
+                        CsvOptions::default()
+                    }
+                };
+                stream(
+                    source,
+                    &mut FilterSink::new(CsvSink::new(output, options), filter),
+                    args,
+                )
+            }
+            OutputFormat::JSON => {
+                let options = JsonOptions {
+                    columnar: args.columnar,
+                };
+                stream(
+                    source,
+                    &mut FilterSink::new(JsonSink::with_options(output, options), filter),
+                    args,
+                )
+            }
+            OutputFormat::NDJSON => stream(source, &mut FilterSink::new(NdjsonSink::new(output), filter), args),
+            OutputFormat::YAML | OutputFormat::YML => {
+                stream(source, &mut FilterSink::new(YamlSink::new(output), filter), args)
+            }
+            OutputFormat::LAS => write_las(source, output, &filter, args),
+        }?;
+    }
+
+    if !is_stdio(&args.output) {
+        println!("Success! Exported '{to}' file to '{}'", args.output);
+    }
+    Ok(())
+}
+
+fn fmt(args: &FmtArgs) -> Result<(), Failure> {
+    if args.in_place && is_stdio(&args.input) {
+        return Err(Failure::new("'--in-place' needs a file, not stdin"));
+    }
+    let (las_file, _) = read_las_file(&args.input, parse_options(args.encoding))?;
+    let options = LasWriterOptions {
+        decimals: args.decimals,
+        uppercase_headers: args.uppercase_headers,
+        line_ending: match args.crlf {
+            true => LineEnding::CRLF,
+            false => LineEnding::LF,
+        },
+        encoding: args.output_encoding.unwrap_or_default(),
+        ..LasWriterOptions::pretty()
+    };
+    if args.in_place {
+        liblas::write_file_atomically(Path::new(&args.input), |writer| {
+            LasWriter::new(writer, options).write(&las_file)
+        })?;
+        return Ok(());
+    }
+    LasWriter::new(open_output(&args.output, args.force)?, options).write(&las_file)?;
+    Ok(())
+}
+
+fn batch(args: &BatchArgs) -> Result<(), Failure> {
+    let options = BatchOptions {
+        output_format: args.to.clone(),
+        out_dir: PathBuf::from(&args.out_dir),
+        workers: args.jobs,
+        overwrite: args.force,
+    };
+    let inputs: Vec<&str> = args.inputs.iter().map(String::as_str).collect();
+    // Only `inputs` themselves, eg. an invalid glob pattern, fail the whole batch.
+    let report = liblas::convert_batch(&inputs, &options).map_err(argument_error)?;
+
+    let mut output = open_output(&args.report, true)?;
+    writeln!(output, "{}", report.to_json_str()?)?;
+    output.flush()?;
+
+    for file in report.files.iter() {
+        if let Some(error) = file.error.as_ref() {
+            eprintln!("failed: {} : {error}", file.input.display());
+        }
+    }
+    eprintln!("{} converted, {} failed", report.succeeded, report.failed);
+    if report.failed > 0 {
+        return Err(Failure::invalid(format!(
+            "{} of {} files failed",
+            report.failed,
+            report.files.len()
+        )));
+    }
+    Ok(())
+}
 
-    let file = file_options.open(&args.out).unwrap_or_else(|e| {
-        println!("Error creating or opening '--out' file : {e}\nYou may need to use the '--force' switch to:\n - Create non-existent directory (or directories) within '--out' path\n - Overwrite existing .json file specified in '--out' path");
-        exit(1);
-    });
+fn main() {
+    let cli = Cli::parse();
 
-    liblas::parse_into(&args.las, file, args.out_type.clone()).map_err(|e| {
-        println!("Error converting .las file to .{} : {e:?}", args.out_type);
-        exit(1);
-    });
+    let result = match &cli.command {
+        Command::Info(args) => info(args),
+        Command::Validate(args) => validate(args),
+        Command::Convert(args) => convert(args),
+        Command::Fmt(args) => fmt(args),
+        Command::Batch(args) => batch(args),
+    };
 
-    println!("Success! Exported '{}' file to '{}'", args.out_type, args.out);
+    if let Err(failure) = result {
+        eprintln!("error: {}", failure.message);
+        exit(failure.code);
+    }
 }
@@ -15,6 +15,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("2.0"),
             description: Some("CWLS LOG ASCII STANDARD -VERSION 2.0".to_string()),
             comments: None,
+            format: None,
+            association: None,
         },
         wrap: DataLine {
             mnemonic: "WRAP".to_string(),
@@ -22,6 +24,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("NO"),
             description: Some("ONE LINE PER DEPTH STEP".to_string()),
             comments: None,
+            format: None,
+            association: None,
         },
         additional: vec![DataLine {
             mnemonic: "CREA".to_string(),
@@ -29,6 +33,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("02-08-2006"),
             description: Some("LAS File Creation Date (MM-DD-YYYY)".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }],
         comments: Some(vec!["Comment before version info".to_string()]),
         header: "~VERSION INFORMATION".to_string(),
@@ -48,6 +54,8 @@ fn main() -> Result<(), ParseError> {
                 "MNEM.UNIT DATA DESCRIPTION".to_string(),
                 "----- ----- ---------- -----------------".to_string(),
             ]),
+            format: None,
+            association: None,
         },
         stop: DataLine {
             mnemonic: "STOP".to_string(),
@@ -55,6 +63,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("1669.7500"),
             description: Some("STOP DEPTH".to_string()),
             comments: None,
+            format: None,
+            association: None,
         },
         step: DataLine {
             mnemonic: "STEP".to_string(),
@@ -62,6 +72,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("-0.1250"),
             description: Some("STEP".to_string()),
             comments: None,
+            format: None,
+            association: None,
         },
         null: DataLine {
             mnemonic: "NULL".to_string(),
@@ -69,6 +81,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("-999.25"),
             description: Some("NULL VALUE".to_string()),
             comments: None,
+            format: None,
+            association: None,
         },
         comp: Some(DataLine {
             mnemonic: "COMP".to_string(),
@@ -76,6 +90,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("ANY OIL COMPANY INC."),
             description: Some("COMPANY".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         well: Some(DataLine {
             mnemonic: "WELL".to_string(),
@@ -83,6 +99,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("ANY ET AL 12-34-12-34"),
             description: Some("WELL".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         fld: Some(DataLine {
             mnemonic: "FLD".to_string(),
@@ -90,6 +108,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("WILDCAT"),
             description: Some("FIELD".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         loc: Some(DataLine {
             mnemonic: "LOC".to_string(),
@@ -97,6 +117,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("12-34-12-34W5M"),
             description: Some("LOCATION".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         prov: Some(DataLine {
             mnemonic: "PROV".to_string(),
@@ -104,6 +126,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("ALBERTA"),
             description: Some("PROVINCE".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         srvc: Some(DataLine {
             mnemonic: "SRVC".to_string(),
@@ -111,6 +135,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("ANY LOGGING COMPANY INC."),
             description: Some("SERVICE COMPANY".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         date: Some(DataLine {
             mnemonic: "DATE".to_string(),
@@ -118,6 +144,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("13-DEC-86"),
             description: Some("LOG DATE".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         uwi: Some(DataLine {
             mnemonic: "UWI".to_string(),
@@ -125,6 +153,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("100123401234W500"),
             description: Some("UNIQUE WELL ID".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }),
         additional: vec![DataLine {
             mnemonic: "LIC".to_string(),
@@ -132,6 +162,8 @@ fn main() -> Result<(), ParseError> {
             value: LasValue::new("23412"),
             description: Some("ERCB LICENCE NUMB".to_string()),
             comments: None,
+            format: None,
+            association: None,
         }],
         comments: None,
         header: "~WELL INFORMATION".to_string(),
@@ -152,6 +184,8 @@ fn main() -> Result<(), ParseError> {
                     "MNEM.UNIT API CODES CURVE DESCRIPTION".to_string(),
                     "------------------ ------------ -------------------".to_string(),
                 ]),
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "DT".to_string(),
@@ -159,6 +193,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("2 SONIC TRANSIT TIME".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "RHOB".to_string(),
@@ -166,6 +202,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("3 BULK DENSITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "NPHI".to_string(),
@@ -173,6 +211,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("4 NEUTRON POROSITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "SFLU".to_string(),
@@ -180,6 +220,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("5 SHALLOW RESISTIVITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "SFLA".to_string(),
@@ -187,6 +229,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("6 SHALLOW RESISTIVITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "ILM".to_string(),
@@ -194,6 +238,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("7 MEDIUM RESISTIVITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "ILD".to_string(),
@@ -201,6 +247,8 @@ fn main() -> Result<(), ParseError> {
                 value: None,
                 description: Some("8 DEEP RESISTIVITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
         ],
         comments: None,
@@ -218,6 +266,8 @@ fn main() -> Result<(), ParseError> {
                     "MNEM.UNIT VALUE DESCRIPTION".to_string(),
                     "-------------- ---------------- ------------------------".to_string(),
                 ]),
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "BHT".to_string(),
@@ -225,6 +275,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("35.5000"),
                 description: Some("BOTTOM HOLE TEMPERATURE".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "CSGL".to_string(),
@@ -232,6 +284,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("124.6"),
                 description: Some("BASE OF CASING".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "MATR".to_string(),
@@ -239,6 +293,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("SAND"),
                 description: Some("NEUTRON MATRIX".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "MDEN".to_string(),
@@ -246,6 +302,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("2710.0000"),
                 description: Some("LOGGING MATRIX DENSITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "RMF".to_string(),
@@ -253,6 +311,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("0.2160"),
                 description: Some("MUD FILTRATE RESISTIVITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
             DataLine {
                 mnemonic: "DFD".to_string(),
@@ -260,6 +320,8 @@ fn main() -> Result<(), ParseError> {
                 value: LasValue::new("1525.0000"),
                 description: Some("DRILL FLUID DENSITY".to_string()),
                 comments: None,
+                format: None,
+                association: None,
             },
         ],
         comments: None,
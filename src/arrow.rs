@@ -0,0 +1,109 @@
+use crate::{
+    LasFile, ParseError,
+    sections::{AsciiLogData, CurveInformation, ParameterInformation, VersionInformation, WellInformation},
+};
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, RecordBatchOptions};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use serde::Serialize;
+use std::{collections::HashMap, fmt, sync::Arc};
+
+// Schema metadata keys. Each holds the JSON of a header section, in the same shape as `LasFile::to_json_str`.
+pub const METADATA_VERSION: &str = "las:VersionInformation";
+pub const METADATA_WELL: &str = "las:WellInformation";
+pub const METADATA_CURVES: &str = "las:CurveInformation";
+pub const METADATA_PARAMETERS: &str = "las:ParameterInformation";
+
+// Field metadata keys, taken from the Curve section.
+pub const FIELD_METADATA_UNIT: &str = "unit";
+pub const FIELD_METADATA_DESCRIPTION: &str = "description";
+
+impl AsciiLogData {
+    /// One nullable `Float64` column per curve. NULL and non-numeric values become nulls.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ParseError> {
+        let schema = log_data_schema(&self.headers, None, HashMap::new());
+        let columns = self.curves.iter().map(|curve| Float64Array::from_iter(curve.iter()));
+        record_batch(schema, columns, self.num_rows())
+    }
+}
+
+impl LasFile {
+    /// Same as [`AsciiLogData::to_record_batch`], plus the unit and description of each curve as field
+    /// metadata and the Version, Well, Curve and Parameter sections as schema metadata.
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ParseError> {
+        let metadata = header_metadata(
+            &self.version_information,
+            &self.well_information,
+            &self.curve_information,
+            self.parameter_information.as_ref(),
+        )?;
+        let schema = log_data_schema(&self.ascii_log_data.headers, Some(&self.curve_information), metadata);
+        let columns = self
+            .ascii_log_data
+            .curves
+            .iter()
+            .map(|curve| Float64Array::from_iter(curve.iter()));
+        record_batch(schema, columns, self.ascii_log_data.num_rows())
+    }
+}
+
+pub(crate) fn log_data_schema(
+    headers: &[String],
+    curves: Option<&CurveInformation>,
+    metadata: HashMap<String, String>,
+) -> SchemaRef {
+    let fields: Vec<Field> = headers
+        .iter()
+        .enumerate()
+        .map(|(i, mnemonic)| {
+            let mut field_metadata = HashMap::new();
+            if let Some(curve) = curves.and_then(|c| c.curves.get(i)) {
+                if let Some(unit) = &curve.unit {
+                    field_metadata.insert(FIELD_METADATA_UNIT.to_string(), unit.clone());
+                }
+                if let Some(description) = &curve.description {
+                    field_metadata.insert(FIELD_METADATA_DESCRIPTION.to_string(), description.clone());
+                }
+            }
+            Field::new(mnemonic, DataType::Float64, true).with_metadata(field_metadata)
+        })
+        .collect();
+    Arc::new(Schema::new_with_metadata(fields, metadata))
+}
+
+pub(crate) fn header_metadata(
+    version: &VersionInformation,
+    well: &WellInformation,
+    curves: &CurveInformation,
+    parameters: Option<&ParameterInformation>,
+) -> Result<HashMap<String, String>, ParseError> {
+    let mut metadata = HashMap::new();
+    metadata.insert(METADATA_VERSION.to_string(), to_json(version)?);
+    metadata.insert(METADATA_WELL.to_string(), to_json(well)?);
+    metadata.insert(METADATA_CURVES.to_string(), to_json(curves)?);
+    if let Some(parameters) = parameters {
+        metadata.insert(METADATA_PARAMETERS.to_string(), to_json(parameters)?);
+    }
+    Ok(metadata)
+}
+
+pub(crate) fn record_batch(
+    schema: SchemaRef,
+    columns: impl Iterator<Item = Float64Array>,
+    num_rows: usize,
+) -> Result<RecordBatch, ParseError> {
+    let columns: Vec<ArrayRef> = columns.map(|column| Arc::new(column) as ArrayRef).collect();
+    // Row count is needed for files without any curves.
+    let options = RecordBatchOptions::new().with_row_count(Some(num_rows));
+    RecordBatch::try_new_with_options(schema, columns, &options).map_err(to_parse_error)
+}
+
+pub(crate) fn to_parse_error(e: impl fmt::Display) -> ParseError {
+    ParseError::Error { message: e.to_string() }
+}
+
+fn to_json<T>(section: &T) -> Result<String, ParseError>
+where
+    T: Serialize,
+{
+    serde_json::to_string(section).map_err(to_parse_error)
+}
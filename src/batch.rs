@@ -0,0 +1,268 @@
+use crate::{OutputFormat, ParseError, parse_into};
+use serde::Serialize;
+use std::{
+    collections::{HashMap, HashSet, hash_map::Entry},
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    num::NonZeroUsize,
+    panic::{AssertUnwindSafe, catch_unwind},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    pub output_format: OutputFormat,
+    /// Converted files go here, in the same tree they were found in.
+    pub out_dir: PathBuf,
+    /// Number of files converted at once. Defaults to the number of CPUs.
+    pub workers: Option<usize>,
+    /// Overwrite files that already exist in `out_dir`, otherwise they are reported as failures.
+    pub overwrite: bool,
+}
+
+/// What happened to every file of a batch, in the order they were found. Serializes to JSON for scripts.
+#[derive(Debug, Default, Serialize)]
+pub struct BatchReport {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub files: Vec<BatchFile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchFile {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    /// The [`ParseError`] the file failed with, in plain words.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchReport {
+    pub fn to_json_str(&self) -> Result<String, ParseError> {
+        serde_json::to_string_pretty(self).map_err(|_| ParseError::ConvertingTo {
+            format: "json".to_string(),
+        })
+    }
+}
+
+/// A .las file found by [`find_las_files`], along with the directory its output path is mirrored from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchInput {
+    pub root: PathBuf,
+    pub path: PathBuf,
+}
+
+/// Every .las file (case insensitive) in `inputs`, which can be files, directories (searched recursively)
+/// or glob patterns like `"archive/**/*.las"`.
+pub fn find_las_files(inputs: &[&str]) -> Result<Vec<BatchInput>, ParseError> {
+    let mut found = vec![];
+    for &input in inputs.iter() {
+        if input.contains(['*', '?', '[']) {
+            let options = glob::MatchOptions {
+                case_sensitive: false,
+                ..Default::default()
+            };
+            let paths = glob::glob_with(input, options).map_err(|e| ParseError::Error {
+                message: format!("Invalid glob pattern '{input}' : {e}"),
+            })?;
+            let root = glob_root(input);
+            for path in paths.flatten().filter(|p| p.is_file()) {
+                found.push(BatchInput {
+                    root: root.clone(),
+                    path,
+                });
+            }
+        } else if Path::new(input).is_dir() {
+            let mut paths = vec![];
+            walk_dir(Path::new(input), &mut paths)?;
+            paths.sort();
+            found.extend(paths.into_iter().map(|path| BatchInput {
+                root: PathBuf::from(input),
+                path,
+            }));
+        } else {
+            let path = PathBuf::from(input);
+            let root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            found.push(BatchInput { root, path });
+        }
+    }
+    // The same file can be matched by more than one input, only the first match is kept.
+    let mut seen = HashSet::new();
+    found.retain(|input| seen.insert(input.path.clone()));
+    Ok(found)
+}
+
+/// Converts every file in `inputs` (see [`find_las_files`]) into `options.out_dir`, on a pool of worker threads.
+///
+/// A file that fails doesn't stop the batch, it is reported along with its error and any partly written
+/// output is removed. Only a problem with `inputs` themselves, eg. an invalid glob pattern, returns `Err`.
+/// When files from different roots end up at the same output path, eg. "a/x.las" and "b/x.las",
+/// only the first one is converted and the others are reported as failed.
+pub fn convert_batch(inputs: &[&str], options: &BatchOptions) -> Result<BatchReport, ParseError> {
+    let files = find_las_files(inputs)?;
+    let outputs: Vec<PathBuf> = files.iter().map(|input| output_path(input, options)).collect();
+    let duplicates = duplicate_outputs(&files, &outputs);
+    let workers = options
+        .workers
+        .or_else(|| thread::available_parallelism().ok().map(NonZeroUsize::get))
+        .unwrap_or(1)
+        .clamp(1, files.len().max(1));
+
+    // Each worker takes the next file until there are none left.
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, BatchFile)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = vec![];
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(input) = files.get(i) else {
+                            break;
+                        };
+                        let file = match &duplicates[i] {
+                            Some(error) => BatchFile {
+                                input: input.path.clone(),
+                                output: outputs[i].clone(),
+                                error: Some(error.clone()),
+                            },
+                            None => convert_file(input, &outputs[i], options),
+                        };
+                        done.push((i, file));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_default())
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+
+    let mut report = BatchReport::default();
+    for (_, file) in results {
+        match file.error {
+            Some(_) => report.failed += 1,
+            None => report.succeeded += 1,
+        }
+        report.files.push(file);
+    }
+    Ok(report)
+}
+
+// For each file, why it can't be converted when an earlier file has the same output path.
+fn duplicate_outputs(files: &[BatchInput], outputs: &[PathBuf]) -> Vec<Option<String>> {
+    let mut claimed: HashMap<&Path, &Path> = HashMap::new();
+    files
+        .iter()
+        .zip(outputs)
+        .map(|(input, output)| match claimed.entry(output) {
+            Entry::Occupied(first) => Some(format!(
+                "'{}' is already the output of '{}'",
+                output.display(),
+                first.get().display()
+            )),
+            Entry::Vacant(entry) => {
+                entry.insert(&input.path);
+                None
+            }
+        })
+        .collect()
+}
+
+fn convert_file(input: &BatchInput, output: &Path, options: &BatchOptions) -> BatchFile {
+    let result = catch_unwind(AssertUnwindSafe(|| write_output(&input.path, output, options))).unwrap_or_else(|_| {
+        Err(ParseError::Error {
+            message: "Converting this file panicked".to_string(),
+        })
+    });
+    BatchFile {
+        input: input.path.clone(),
+        output: output.to_path_buf(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Writes `path` through a temporary file in the same directory, which only replaces `path` once `write` has
+/// succeeded. When anything fails the temporary file is removed, and a file already at `path` is left as it was.
+pub fn write_file_atomically<F>(path: &Path, write: F) -> Result<(), ParseError>
+where
+    F: FnOnce(&mut dyn Write) -> Result<(), ParseError>,
+{
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+    let result = File::create(&temp_path).map_err(ParseError::from).and_then(|file| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        Ok(fs::rename(&temp_path, path)?)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+fn write_output(input: &Path, output: &Path, options: &BatchOptions) -> Result<(), ParseError> {
+    if output.exists() {
+        // Paths are compared as found on disk, "archive/a.las" and "./archive/a.las" are the same file.
+        if fs::canonicalize(input)? == fs::canonicalize(output)? {
+            return Err(ParseError::Error {
+                message: "Output would overwrite the input".to_string(),
+            });
+        }
+        if !options.overwrite {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("'{}' already exists", output.display()),
+            )
+            .into());
+        }
+    }
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let input = input.to_string_lossy();
+    write_file_atomically(output, |writer| {
+        parse_into(&input, writer, options.output_format.clone())
+    })
+}
+
+// `out_dir` followed by the path of the file within its root, with the extension of the output format.
+fn output_path(input: &BatchInput, options: &BatchOptions) -> PathBuf {
+    let relative = match input.path.strip_prefix(&input.root) {
+        Ok(relative) if relative.file_name().is_some() => relative.to_path_buf(),
+        _ => PathBuf::from(input.path.file_name().unwrap_or_default()),
+    };
+    options
+        .out_dir
+        .join(relative)
+        .with_extension(options.output_format.extension())
+}
+
+fn walk_dir(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), ParseError> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            walk_dir(&path, found)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("las"))
+        {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Leading directories of a glob pattern without wildcards, eg. "archive/2024" for "archive/2024/**/*.las".
+fn glob_root(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|c| !c.as_os_str().to_string_lossy().contains(['*', '?', '[']))
+        .collect()
+}
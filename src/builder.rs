@@ -0,0 +1,397 @@
+use crate::{
+    DataLine, Delimiter, LasFile, LasValue, ParseError,
+    parse::{Section, SectionEntry, validate_curve_mnemonics},
+    resample::DEPTH_EPSILON,
+    sections::{AsciiLogData, CurveInformation, ParameterInformation, VersionInformation, WellInformation},
+};
+use serde::{
+    Deserialize, Deserializer,
+    de::{MapAccess, Visitor},
+};
+use serde_json::Value;
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Read},
+};
+
+/// Builds a [`LasFile`] (LAS 2.0, unwrapped) from typed values.
+///
+/// "STRT", "STOP" and "STEP" are computed from the index curve (the first curve) unless set
+/// explicitly, and the result is validated with the same rules the parser uses.
+///
+/// ```ignore
+/// let las = LasFileBuilder::new()
+///     .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+///     .well_info("PROV", "ALBERTA", "PROVINCE")
+///     .curve("DEPT", "M", "DEPTH")
+///     .curve("GR", "API", "GAMMA RAY")
+///     .row([1670.0, 81.2])
+///     .row([Some(1670.5), None])
+///     .build()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct LasFileBuilder {
+    start: Option<f64>,
+    stop: Option<f64>,
+    step: Option<f64>,
+    null: f64,
+    decimals: Option<usize>,
+    well: Vec<DataLine>,
+    parameters: Vec<DataLine>,
+    curves: Vec<DataLine>,
+    rows: Vec<Vec<Option<f64>>>,
+}
+
+impl Default for LasFileBuilder {
+    fn default() -> Self {
+        Self {
+            start: None,
+            stop: None,
+            step: None,
+            null: -999.25,
+            decimals: None,
+            well: vec![],
+            parameters: vec![],
+            curves: vec![],
+            rows: vec![],
+        }
+    }
+}
+
+impl LasFileBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the "STRT" computed from the data.
+    pub fn start(mut self, start: f64) -> Self {
+        self.start = Some(start);
+        self
+    }
+
+    /// Overrides the "STOP" computed from the data.
+    pub fn stop(mut self, stop: f64) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    /// Overrides the "STEP" computed from the data.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// "NULL" value, -999.25 by default. Missing values in rows are written as this.
+    pub fn null_value(mut self, null: f64) -> Self {
+        self.null = null;
+        self
+    }
+
+    /// Write numbers with a fixed number of decimals, eg. `4` for "1670.0000".
+    /// By default numbers are written as short as possible without losing precision.
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Any other Well section line, eg. `well_info("UWI", "100123401234W500", "UNIQUE WELL ID")`.
+    /// Mnemonics are handled the same way as when parsing, so "UWI" ends up in `WellInformation.uwi`.
+    pub fn well_info(mut self, mnemonic: &str, value: &str, description: &str) -> Self {
+        self.well.push(data_line(mnemonic, "", value, description));
+        self
+    }
+
+    pub fn parameter(mut self, mnemonic: &str, unit: &str, value: &str, description: &str) -> Self {
+        self.parameters.push(data_line(mnemonic, unit, value, description));
+        self
+    }
+
+    /// Adds a curve (column). The first curve is the index and must be "DEPT", "DEPTH", "TIME" or "INDEX".
+    pub fn curve(mut self, mnemonic: &str, unit: &str, description: &str) -> Self {
+        self.curves.push(data_line(mnemonic, unit, "", description));
+        self
+    }
+
+    /// Adds a row with one value per curve. `None` and NaN are written as "NULL".
+    pub fn row<I, V>(mut self, row: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<Option<f64>>,
+    {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn rows<R, I, V>(self, rows: R) -> Self
+    where
+        R: IntoIterator<Item = I>,
+        I: IntoIterator<Item = V>,
+        V: Into<Option<f64>>,
+    {
+        rows.into_iter().fold(self, |builder, row| builder.row(row))
+    }
+
+    /// Curves and rows from CSV (or TSV), in the shape written by [`crate::CsvSink`]:
+    /// lines starting with '#' are skipped, headers look like "GR[API]" and empty cells are NULL.
+    pub fn from_csv<R>(reader: R, separator: char) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let delimiter = match separator {
+            '\t' => Delimiter::Tab,
+            ',' => Delimiter::Comma,
+            other => {
+                return Err(ParseError::Error {
+                    message: format!("Unsupported CSV separator {other:?}! Expected ',' or a tab."),
+                });
+            }
+        };
+
+        let mut builder = LasFileBuilder::new();
+        let mut has_headers = false;
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let cells = delimiter.split(&line);
+
+            if !has_headers {
+                for label in cells {
+                    let (mnemonic, unit) = match label.strip_suffix(']').and_then(|l| l.split_once('[')) {
+                        Some((mnemonic, unit)) => (mnemonic.to_string(), unit.to_string()),
+                        None => (label, String::new()),
+                    };
+                    builder = builder.curve(&mnemonic, &unit, "");
+                }
+                has_headers = true;
+                continue;
+            }
+
+            let mut row = Vec::with_capacity(cells.len());
+            for cell in cells {
+                if cell.is_empty() {
+                    row.push(None);
+                    continue;
+                }
+                let value = cell.parse::<f64>().map_err(|_| ParseError::InvalidAsciiValue {
+                    raw_value: cell.clone(),
+                    line_number: i + 1,
+                })?;
+                row.push(Some(value));
+            }
+            builder = builder.row(row);
+        }
+
+        Ok(builder)
+    }
+
+    /// Curves and rows from newline delimited JSON, in the shape written by [`crate::NdjsonSink`]: one object per
+    /// depth step keyed by curve mnemonic, `null` for NULL. Curves (and their units) come from "CurveInformation"
+    /// in the header record when there is one, otherwise from the keys of the first row.
+    pub fn from_json_rows<R>(reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let mut builder = LasFileBuilder::new();
+
+        for (i, line) in BufReader::new(reader).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line_number = i + 1;
+            let json_error = |e: serde_json::Error| ParseError::Error {
+                message: format!("line {line_number}: {e}"),
+            };
+            let JsonObject(entries) = serde_json::from_str(&line).map_err(json_error)?;
+
+            // The header record, every other line is a row.
+            if let Some((_, curves)) = entries.iter().find(|(key, _)| key == "CurveInformation") {
+                builder.curves = CurveInformation::deserialize(curves).map_err(json_error)?.curves;
+                continue;
+            }
+            if builder.curves.is_empty() {
+                for (mnemonic, _) in entries.iter() {
+                    builder = builder.curve(mnemonic, "", "");
+                }
+            }
+
+            let mut row = vec![None; builder.curves.len()];
+            for (mnemonic, value) in entries {
+                let Some(column) = builder.curves.iter().position(|c| c.mnemonic == mnemonic) else {
+                    return Err(ParseError::Error {
+                        message: format!("line {line_number}: {mnemonic:?} is not a curve"),
+                    });
+                };
+                row[column] = match value {
+                    Value::Null => None,
+                    Value::Number(n) => n.as_f64(),
+                    other => {
+                        return Err(ParseError::InvalidAsciiValue {
+                            raw_value: other.to_string(),
+                            line_number,
+                        });
+                    }
+                };
+            }
+            builder = builder.row(row);
+        }
+
+        Ok(builder)
+    }
+
+    pub fn build(self) -> Result<LasFile, ParseError> {
+        let mnemonics: Vec<String> = self.curves.iter().map(|c| c.mnemonic.clone()).collect();
+        validate_curve_mnemonics(&mnemonics)?;
+
+        let mut ascii_rows = Vec::with_capacity(self.rows.len());
+        for (i, row) in self.rows.iter().enumerate() {
+            if row.len() != mnemonics.len() {
+                // Rows are numbered from 1, like lines.
+                return Err(ParseError::AsciiColumnsMismatch {
+                    line_number: i + 1,
+                    num_cols_in_headers: mnemonics.len(),
+                    num_cols_in_row: row.len(),
+                });
+            }
+            if row.first().is_some_and(Option::is_none) {
+                return Err(ParseError::Error {
+                    message: format!(
+                        "Row {} has no {:?} value, every row needs an index value!",
+                        i + 1,
+                        mnemonics[0]
+                    ),
+                });
+            }
+            ascii_rows.push(row.iter().map(|v| self.format_cell(*v)).collect());
+        }
+
+        let version = VersionInformation::try_from(self.section(
+            "Version Information",
+            vec![
+                data_line("VERS", "", "2.0", "CWLS LOG ASCII STANDARD - VERSION 2.0"),
+                data_line("WRAP", "", "NO", "One line per depth step"),
+            ],
+        )?)?;
+
+        let unit = self.curves[0].unit.clone().unwrap_or_default();
+        let (start, stop, step) = self.index_range()?;
+        let mut well_lines = vec![
+            data_line("STRT", &unit, &self.format_value(start), "START DEPTH"),
+            data_line("STOP", &unit, &self.format_value(stop), "STOP DEPTH"),
+            data_line("STEP", &unit, &self.format_value(step), "STEP"),
+            data_line("NULL", "", &self.format_value(self.null), "NULL VALUE"),
+        ];
+        well_lines.extend(self.well.iter().cloned());
+        let well = WellInformation::try_from(self.section("Well Information", well_lines)?)?;
+
+        let curves = CurveInformation::try_from(self.section("Curve Information", self.curves.clone())?)?;
+
+        let parameters = match self.parameters.is_empty() {
+            true => None,
+            false => Some(ParameterInformation::try_from(
+                self.section("Parameter Information", self.parameters.clone())?,
+            )?),
+        };
+
+        let mut data_section = Section::new("A".to_string(), 0, false)?;
+        data_section.ascii_headers = Some(mnemonics);
+        data_section.ascii_rows = ascii_rows;
+        let ascii_log_data = AsciiLogData::try_from(data_section)?;
+
+        Ok(LasFile::new(version, well, curves, ascii_log_data, None, parameters))
+    }
+
+    fn section(&self, name: &str, lines: Vec<DataLine>) -> Result<Section, ParseError> {
+        let mut section = Section::new(name.to_string(), 0, false)?;
+        section.entries = lines.into_iter().map(SectionEntry::Delimited).collect();
+        Ok(section)
+    }
+
+    // STRT/STOP are the first and last index values. STEP is the spacing between them when
+    // it is constant, or 0 when it is not (per CWLS v2.0).
+    fn index_range(&self) -> Result<(f64, f64, f64), ParseError> {
+        // `build` has made sure every row has an index value.
+        let index: Vec<f64> = self.rows.iter().filter_map(|row| row[0]).collect();
+
+        let steps: Vec<f64> = index.windows(2).map(|w| w[1] - w[0]).collect();
+        let regular = steps
+            .windows(2)
+            .all(|w| (w[0] - w[1]).abs() <= DEPTH_EPSILON * w[0].abs().max(1.0));
+        let computed_step = match (steps.len(), regular) {
+            (0, _) | (_, false) => 0.0,
+            // Averaged and rounded, so float error between rows doesn't end up in the header.
+            (n, true) => (((index[n] - index[0]) / n as f64) * 1e9).round() / 1e9,
+        };
+
+        let missing = |mnemonic: &str| ParseError::WellDataMissingRequiredValueForMnemonic {
+            mnemonic: mnemonic.to_string(),
+        };
+        let start = self.start.or(index.first().copied()).ok_or_else(|| missing("STRT"))?;
+        let stop = self.stop.or(index.last().copied()).ok_or_else(|| missing("STOP"))?;
+        let step = self.step.unwrap_or(computed_step);
+
+        Ok((start, stop, step))
+    }
+
+    fn format_cell(&self, value: Option<f64>) -> String {
+        match value {
+            Some(v) if !v.is_nan() => self.format_value(v),
+            _ => self.format_value(self.null),
+        }
+    }
+
+    fn format_value(&self, value: f64) -> String {
+        match self.decimals {
+            Some(decimals) => format!("{value:.decimals$}"),
+            None => value.to_string(),
+        }
+    }
+}
+
+fn data_line(mnemonic: &str, unit: &str, value: &str, description: &str) -> DataLine {
+    DataLine {
+        mnemonic: mnemonic.to_string(),
+        unit: Some(unit.to_string()).filter(|u| !u.is_empty()),
+        value: LasValue::new(value),
+        description: Some(description.to_string()).filter(|d| !d.is_empty()),
+        comments: None,
+        format: None,
+        association: None,
+    }
+}
+
+// A JSON object with its keys in the order they were written, which is the order of the curves.
+struct JsonObject(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for JsonObject {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(JsonObjectVisitor)
+    }
+}
+
+struct JsonObjectVisitor;
+
+impl<'de> Visitor<'de> for JsonObjectVisitor {
+    type Value = JsonObject;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a JSON object")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<JsonObject, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(JsonObject(entries))
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt;
+
+use crate::ParseError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The file breaks the spec but nothing was lost, eg. a missing "UWI".
+    Warning,
+    /// Data had to be skipped, padded or truncated to keep going.
+    Error,
+}
+
+/// A problem found while parsing in [`crate::Strictness::Lenient`] mode.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Line the problem was found on, `None` for problems with the file as a whole, eg. a missing section.
+    pub line_number: Option<usize>,
+    /// The original text of that line.
+    pub line: Option<String>,
+    /// The rule that was violated. In strict mode this is the error that would have been returned.
+    pub rule: ParseError,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(severity: Severity, rule: ParseError) -> Self {
+        Self {
+            severity,
+            line_number: None,
+            line: None,
+            rule,
+        }
+    }
+
+    pub(crate) fn on_line(mut self, line_number: usize, line: &str) -> Self {
+        self.line_number = Some(line_number);
+        self.line = Some(line.to_string());
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}")?;
+        if let Some(line_number) = self.line_number {
+            write!(f, " (line {line_number})")?;
+        }
+        write!(f, ": {}", self.rule)?;
+        if let Some(line) = &self.line {
+            write!(f, "\n    {}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
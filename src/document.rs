@@ -0,0 +1,353 @@
+use crate::{
+    Encoding, LasFile, ParseError,
+    parse::{AstSink, Delimiter, LasParser, LineDelimiters, SectionHeader, SectionKind},
+    tokenizer::{LasToken, LasTokenizer},
+};
+use std::{
+    fmt,
+    fs::File,
+    io::{Read, Write},
+    ops::Range,
+};
+
+/// A LAS file as it was written: every line, with its original spacing, comments, blank lines and
+/// line endings. Use it for edits that must leave everything else byte-for-byte the same.
+///
+/// ```ignore
+/// let mut doc = LasDocument::open("/some/file.las")?;
+/// doc.set_value(SectionKind::Well, "COMP", "ANY OIL COMPANY LTD.")?;
+/// doc.set_cell(0, 1, "-999.25")?;
+/// doc.write_to(File::create("/some/file.las")?)?; // Only those two lines changed
+/// ```
+#[derive(Debug, Clone)]
+pub struct LasDocument {
+    // Each line keeps its line ending, so joining them gives back the original file.
+    lines: Vec<String>,
+    data_lines: Vec<DataLineRef>,
+    // (line index, index of its first value among all values in "~A") for each line of ASCII log data.
+    ascii_lines: Vec<(usize, usize)>,
+    num_columns: usize,
+    delimiter: Delimiter,
+    las3: bool,
+    // What the file was read as, and is written back as.
+    encoding: Encoding,
+}
+
+#[derive(Debug, Clone)]
+struct DataLineRef {
+    kind: SectionKind,
+    mnemonic: String,
+    line: usize,
+}
+
+impl LasDocument {
+    pub fn open(las_file_path: &str) -> Result<Self, ParseError> {
+        Self::from_reader(File::open(las_file_path)?)
+    }
+
+    /// Reads the whole file into memory. It has to parse without errors, so we know
+    /// which lines are data lines and rows of ASCII log data.
+    ///
+    /// Files that aren't UTF-8 are read as Windows-1252, same as [`crate::parse`], and written back in
+    /// the encoding they were read as (byte order mark included).
+    pub fn from_reader<R>(mut reader: R) -> Result<Self, ParseError>
+    where
+        R: Read,
+    {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        let mut encoding = Encoding::Auto;
+        let bytes = encoding.strip_bom(&bytes);
+        let text = encoding.decode_or_fallback(bytes).unwrap_or_default();
+        let las_file = parse_str(&text)?;
+
+        let mut doc = Self {
+            lines: text.split_inclusive('\n').map(str::to_string).collect(),
+            data_lines: vec![],
+            ascii_lines: vec![],
+            num_columns: las_file.ascii_log_data.headers.len(),
+            delimiter: las_file.version_information.delimiter(),
+            las3: las_file.version_information.is_las3(),
+            encoding,
+        };
+        doc.index(&text)?;
+        Ok(doc)
+    }
+
+    /// Parses the document as it is now, including any edits.
+    pub fn to_las_file(&self) -> Result<LasFile, ParseError> {
+        let mut las_file = parse_str(&self.to_las_str())?;
+        las_file.encoding = self.encoding;
+        Ok(las_file)
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn to_las_str(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn write_to<W>(&self, mut writer: W) -> Result<(), ParseError>
+    where
+        W: Write,
+    {
+        writer.write_all(self.encoding.bom())?;
+        for line in self.lines.iter() {
+            writer.write_all(&self.encoding.try_encode(line)?)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Replaces the value of the first data line with `mnemonic` (case insensitive) in the first section of `kind`,
+    /// eg. `set_value(SectionKind::Well, "STRT", "1670.0000")`. Spacing around the value is kept where it fits,
+    /// so the colon and description stay where they were.
+    pub fn set_value(&mut self, kind: SectionKind, mnemonic: &str, value: &str) -> Result<(), ParseError> {
+        check_single_line(value)?;
+        let line_index = self
+            .data_lines
+            .iter()
+            .find(|d| d.kind == kind && d.mnemonic.eq_ignore_ascii_case(mnemonic))
+            .map(|d| d.line)
+            .ok_or_else(|| ParseError::Error {
+                message: format!("No data line with mnemonic '{mnemonic}' in {kind:?} section!"),
+            })?;
+
+        let line = &self.lines[line_index];
+        let (content, _) = split_line_ending(line);
+        // Same as the parser, a LAS 3.0 "| association" may contain a colon.
+        let without_association = match (self.las3, content.rfind('|')) {
+            (true, Some(i)) => &content[..i],
+            _ => content,
+        };
+        let ld = LineDelimiters::find_in(without_association);
+        let (Some(space), Some(colon)) = (
+            ld.space,
+            ld.colon.filter(|&colon| colon >= ld.space.unwrap_or_default()),
+        ) else {
+            return Err(ParseError::MissingDelimiter {
+                delimiter: "Missing ' ' or ':' in line! Can't tell where the value is.".to_string(),
+                line_number: line_index + 1,
+                line: content.to_string(),
+            });
+        };
+
+        let region = space..colon;
+        let new_line = match content[region.clone()].trim().is_empty() {
+            // No value yet : "STRT.M   : START DEPTH" -> "STRT.M 1670.0 : START DEPTH"
+            true => {
+                let padding = (region.len().saturating_sub(value.len() + 1)).max(1);
+                replace_range(line, region, &format!(" {value}{}", " ".repeat(padding)))
+            }
+            false => replace_aligned(line, region, value, 1),
+        };
+        self.lines[line_index] = new_line;
+        Ok(())
+    }
+
+    /// Replaces a single value of ASCII log data, by row (depth step) and column (curve), both from 0.
+    /// Works for wrapped data too. The value keeps the right edge of the one it replaces where it fits,
+    /// so the rest of the line doesn't move.
+    pub fn set_cell(&mut self, row: usize, column: usize, value: &str) -> Result<(), ParseError> {
+        check_single_line(value)?;
+        if column >= self.num_columns {
+            return Err(ParseError::Error {
+                message: format!(
+                    "Column {column} is out of range, there are {} curves!",
+                    self.num_columns
+                ),
+            });
+        }
+        let target = row * self.num_columns + column;
+        let position = self.ascii_lines.partition_point(|&(_, first)| first <= target);
+        let out_of_range = || ParseError::Error {
+            message: format!("Row {row} is out of range!"),
+        };
+        let &(line_index, first) = position
+            .checked_sub(1)
+            .and_then(|i| self.ascii_lines.get(i))
+            .ok_or_else(out_of_range)?;
+
+        let (content, _) = split_line_ending(&self.lines[line_index]);
+        let spans = self.value_spans(content);
+        let span = spans.get(target - first).ok_or_else(out_of_range)?;
+
+        let value = match self.las3 {
+            true => self.delimiter.quote(value),
+            false => value.to_string(),
+        };
+        let (region, min_gap) = match self.delimiter {
+            // Whitespace before the value is what lets it move left.
+            Delimiter::Space => {
+                let start = content[..span.start].trim_end().len();
+                (start..span.end, usize::from(start > 0))
+            }
+            _ => (span.clone(), 0),
+        };
+        let new_line = replace_aligned(&self.lines[line_index], region, &value, min_gap);
+
+        // A value that splits into more than one would shift every value after it.
+        if self.value_spans(split_line_ending(&new_line).0).len() != spans.len() {
+            return Err(ParseError::Error {
+                message: format!("Value {value:?} is not a single ASCII log data value!"),
+            });
+        }
+        self.lines[line_index] = new_line;
+        Ok(())
+    }
+
+    // Works out which line each data line and line of ASCII log data is on.
+    fn index(&mut self, text: &str) -> Result<(), ParseError> {
+        let mut kind = None;
+        let mut num_values = 0;
+
+        for token in LasTokenizer::new(text.as_bytes()) {
+            let (raw, line_number) = match token? {
+                LasToken::SectionHeader { name, line_number } => {
+                    kind = Some(SectionHeader::parse(name, line_number, self.las3)?.kind);
+                    continue;
+                }
+                LasToken::DataLine { raw, line_number } => (raw, line_number),
+                _ => continue,
+            };
+            match kind {
+                Some(SectionKind::AsciiLogData) => {
+                    self.ascii_lines.push((line_number - 1, num_values));
+                    num_values += self.value_spans(&raw).len();
+                }
+                Some(
+                    kind @ (SectionKind::Version
+                    | SectionKind::Well
+                    | SectionKind::Curve
+                    | SectionKind::Parameter
+                    | SectionKind::GroupParameter
+                    | SectionKind::GroupDefinition),
+                ) => {
+                    if let Some(period) = raw.find('.') {
+                        self.data_lines.push(DataLineRef {
+                            kind,
+                            mnemonic: raw[..period].trim().to_string(),
+                            line: line_number - 1,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // Byte ranges of each value in a line of ASCII log data, split the same way the parser does.
+    fn value_spans(&self, raw: &str) -> Vec<Range<usize>> {
+        let separator = match self.delimiter {
+            Delimiter::Space => return whitespace_spans(raw, self.las3),
+            Delimiter::Comma => b',',
+            Delimiter::Tab => b'\t',
+        };
+        let mut spans = vec![];
+        let mut start = 0;
+        let mut in_quotes = false;
+        for (i, b) in raw.bytes().enumerate() {
+            match b {
+                b'"' => in_quotes = !in_quotes,
+                b if b == separator && !in_quotes => {
+                    spans.push(trimmed_span(raw, start..i));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        spans.push(trimmed_span(raw, start..raw.len()));
+        spans
+    }
+}
+
+impl fmt::Display for LasDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.lines.iter() {
+            f.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_str(text: &str) -> Result<LasFile, ParseError> {
+    let tokenizer = LasTokenizer::new(text.as_bytes());
+    let mut parser = LasParser::new(tokenizer);
+    let mut sink = AstSink::new();
+    parser.parse_into(&mut sink)?;
+    LasFile::try_from(sink)
+}
+
+fn check_single_line(value: &str) -> Result<(), ParseError> {
+    if value.contains(['\n', '\r']) {
+        return Err(ParseError::Error {
+            message: format!("Value {value:?} can't contain a line break!"),
+        });
+    }
+    Ok(())
+}
+
+// "STRT.M 1670.0 : START DEPTH\r\n" -> ("STRT.M 1670.0 : START DEPTH", "\r\n")
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let content = line.trim_end_matches(['\n', '\r']);
+    (content, &line[content.len()..])
+}
+
+fn replace_range(line: &str, range: Range<usize>, with: &str) -> String {
+    format!("{}{with}{}", &line[..range.start], &line[range.end..])
+}
+
+// Replaces the value within `region` (the value plus the whitespace around it), keeping its alignment.
+// A value with nothing but whitespace after it in `region` is left aligned, otherwise it's right aligned.
+// Longer values eat into the padding, leaving at least `min_gap` spaces.
+fn replace_aligned(line: &str, region: Range<usize>, value: &str, min_gap: usize) -> String {
+    let text = &line[region.clone()];
+    let lead = text.len() - text.trim_start().len();
+    let trail = text.len() - text.trim_end().len();
+    let old_len = text.trim().len();
+
+    let new_text = if trail > 0 {
+        let padding = (trail + old_len).saturating_sub(value.len()).max(1);
+        format!("{}{value}{}", &text[..lead], " ".repeat(padding))
+    } else {
+        let padding = (lead + old_len).saturating_sub(value.len()).max(min_gap.min(lead));
+        format!("{}{value}", " ".repeat(padding))
+    };
+    replace_range(line, region, &new_text)
+}
+
+fn trimmed_span(raw: &str, range: Range<usize>) -> Range<usize> {
+    let text = &raw[range.clone()];
+    let start = range.start + (text.len() - text.trim_start().len());
+    start..start + text.trim().len()
+}
+
+// LAS 2.0 values are split on whitespace. LAS 3.0 values may be wrapped in double quotes to contain spaces.
+fn whitespace_spans(raw: &str, quoted: bool) -> Vec<Range<usize>> {
+    let bytes = raw.as_bytes();
+    let mut spans = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        if quoted && bytes[i] == b'"' {
+            i += 1;
+            while i < bytes.len() && bytes[i] != b'"' {
+                i += 1;
+            }
+            i = (i + 1).min(bytes.len());
+        } else {
+            while i < bytes.len() && !bytes[i].is_ascii_whitespace() {
+                i += 1;
+            }
+        }
+        spans.push(start..i);
+    }
+    spans
+}
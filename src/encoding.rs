@@ -0,0 +1,108 @@
+use std::{borrow::Cow, fmt, io};
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Text encoding of a .las file. LAS files predate UTF-8 being common, and older ones (especially written on
+/// Windows) often use a single byte encoding for characters like "°" or "µ" in descriptions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Encoding {
+    /// UTF-8, falling back to Windows-1252 for files that aren't valid UTF-8.
+    #[default]
+    Auto,
+    Utf8,
+    /// UTF-8 starting with a byte order mark, which some Windows tools write. Files read as UTF-8 that start
+    /// with one end up with this encoding, so the mark is written back.
+    #[value(name = "utf8-bom")]
+    Utf8Bom,
+    /// The Windows "ANSI" code page for western languages, a superset of ISO-8859-1 (Latin-1).
+    #[value(name = "windows-1252")]
+    Windows1252,
+    /// ISO-8859-1 (Latin-1). Every byte is the character with the same code point.
+    #[value(name = "latin1")]
+    Latin1,
+}
+
+impl Encoding {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Auto => "auto",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 with BOM",
+            Encoding::Windows1252 => "Windows-1252",
+            Encoding::Latin1 => "ISO-8859-1",
+        }
+    }
+
+    // None when `bytes` aren't valid in this encoding, which only happens for UTF-8. Both single byte
+    // encodings map all 256 bytes to a character, so decoding and encoding again gives back the same bytes.
+    pub(crate) fn decode<'a>(&self, bytes: &'a [u8]) -> Option<Cow<'a, str>> {
+        match self {
+            Encoding::Auto | Encoding::Utf8 | Encoding::Utf8Bom => std::str::from_utf8(bytes).ok().map(Cow::Borrowed),
+            Encoding::Windows1252 => Some(encoding_rs::WINDOWS_1252.decode_without_bom_handling(bytes).0),
+            Encoding::Latin1 => Some(Cow::Owned(bytes.iter().map(|&b| char::from(b)).collect())),
+        }
+    }
+
+    // Same as `decode`, except that Auto falls back to (and switches to) Windows-1252 for bytes that aren't UTF-8.
+    pub(crate) fn decode_or_fallback<'a>(&mut self, bytes: &'a [u8]) -> Option<Cow<'a, str>> {
+        match self.decode(bytes) {
+            None if *self == Encoding::Auto => {
+                *self = Encoding::Windows1252;
+                self.decode(bytes)
+            }
+            text => text,
+        }
+    }
+
+    // The start of a file without its byte order mark, when it has one and is read as UTF-8.
+    pub(crate) fn strip_bom<'a>(&mut self, bytes: &'a [u8]) -> &'a [u8] {
+        match bytes.strip_prefix(UTF8_BOM) {
+            Some(rest) if matches!(self, Encoding::Auto | Encoding::Utf8 | Encoding::Utf8Bom) => {
+                *self = Encoding::Utf8Bom;
+                rest
+            }
+            _ => bytes,
+        }
+    }
+
+    // Written before anything else.
+    pub(crate) fn bom(&self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8Bom => UTF8_BOM,
+            _ => &[],
+        }
+    }
+
+    // None when `text` has characters this encoding doesn't have, eg. "€" in ISO-8859-1.
+    pub(crate) fn encode<'a>(&self, text: &'a str) -> Option<Cow<'a, [u8]>> {
+        match self {
+            Encoding::Auto | Encoding::Utf8 | Encoding::Utf8Bom => Some(Cow::Borrowed(text.as_bytes())),
+            Encoding::Windows1252 => {
+                let (bytes, _, had_errors) = encoding_rs::WINDOWS_1252.encode(text);
+                (!had_errors).then_some(bytes)
+            }
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).ok())
+                .collect::<Option<Vec<u8>>>()
+                .map(Cow::Owned),
+        }
+    }
+
+    // Same as `encode`, with an error naming the first character that can't be written.
+    pub(crate) fn try_encode<'a>(&self, text: &'a str) -> io::Result<Cow<'a, [u8]>> {
+        self.encode(text).ok_or_else(|| {
+            let c = text.chars().find(|c| self.encode(c.encode_utf8(&mut [0; 4])).is_none());
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("'{}' can't be written as {self}", c.unwrap_or_default()),
+            )
+        })
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -92,6 +92,55 @@ pub enum ParseError {
         got: String,
         expected_one_of: Vec<String>,
     },
+    UnrecognizedSection {
+        name: String,
+        line_number: usize,
+    },
+    // "STEP" points away from "STOP", eg. STRT 100, STOP 200, STEP -0.5
+    InconsistentStep {
+        start: f64,
+        stop: f64,
+        step: f64,
+    },
+    UnknownUnit {
+        unit: String,
+    },
+    // A curve asked for by mnemonic, eg. to select or convert it, isn't in the file.
+    MissingCurve {
+        mnemonic: String,
+    },
+    IncompatibleUnits {
+        from: String,
+        to: String,
+    },
+    // First or last depth of ASCII log data doesn't match "STRT" or "STOP" (`mnemonic`).
+    DepthMismatch {
+        mnemonic: String,
+        expected: f64,
+        found: f64,
+    },
+    // Consecutive depths are `found` apart instead of "STEP".
+    InconsistentDepthStep {
+        step: f64,
+        found: f64,
+        depth: f64,
+    },
+    // Depth doesn't carry on in the same direction as the depths before it.
+    NonMonotonicIndex {
+        previous: f64,
+        depth: f64,
+    },
+    // "STEP" is 0 (irregular sampling) but every depth is `step` apart.
+    UndeclaredStep {
+        step: f64,
+    },
+    // Run `run` of a well being merged disagrees with the first run on `field`, eg. "UWI" or "depth unit".
+    MismatchedRuns {
+        run: String,
+        field: String,
+        expected: String,
+        found: String,
+    },
 }
 
 impl From<io::Error> for ParseError {
@@ -102,139 +151,151 @@ impl From<io::Error> for ParseError {
 
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ParseError::Io(error) => write!(f, "ParseError::Io({:?})", error),
-            ParseError::Error { message } => write!(f, "ParseError::Error(message: {message})"),
-            ParseError::MissingSection { section } => {
-                write!(f, "ParseError::MissingSection({:?})", section)
-            }
+        let with_line = |message: String, line_number: &usize| format!("line {line_number}: {message}");
+        let message = match self {
+            ParseError::Io(error) => error.to_string(),
+            ParseError::Error { message } => message.clone(),
+            ParseError::MissingSection { section } => format!("the {section:?} section is missing"),
             ParseError::MissingCurveSectionOrAsciiLogsNotLastSectioon => {
-                write!(f, "ParseError::MissingCurveSectionOrAsciiLogsNotLastSectioon")
+                "the Curve section is missing, or the ASCII log data (~A) section isn't the last section".to_string()
             }
             ParseError::MissingMultipleSections { missing_sections } => {
-                write!(f, "ParseError::MissingMultipleSections({:?})", missing_sections)
+                let names: Vec<String> = missing_sections.iter().map(|s| format!("{s:?}")).collect();
+                format!("these sections are missing: {}", names.join(", "))
             }
             ParseError::UnexpectedSection { expected, got } => {
-                write!(
-                    f,
-                    "ParseError::UnexpectedSection(expected={:?}, got={:?})",
-                    expected, got
-                )
+                format!("expected the {expected:?} section, found the {got:?} section")
             }
-            ParseError::MissingRequiredKey { key, line_number, line } => {
-                write!(
-                    f,
-                    "ParseError::MissingRequiredKey(key={:?}, line_number={}, line={:?})",
-                    key, line_number, line
-                )
+            ParseError::MissingRequiredKey { key, line_number, .. } => {
+                with_line(format!("'{key}' is missing"), line_number)
             }
             ParseError::MissingDelimiter {
-                delimiter,
-                line_number,
-                line,
-            } => {
-                write!(
-                    f,
-                    "ParseError::MissingDelimiter(delimiter={:?}, line_number={}, line={:?})",
-                    delimiter, line_number, line
-                )
-            }
+                delimiter, line_number, ..
+            } => with_line(format!("missing '{delimiter}'"), line_number),
             ParseError::SectionMissingRequiredData { section, one_of } => {
-                write!(
-                    f,
-                    "ParseError::SectionMissingRequiredData(section={:?}, one_of={:?})",
-                    section, one_of
-                )
-            }
-            ParseError::InvalidWellValue { mnemonic, value } => {
-                write!(
-                    f,
-                    "ParseError::InvalidWellValue(mnemonic={:?}, value={:?})",
-                    mnemonic, value
-                )
+                format!("the {section:?} section needs one of {}", one_of.join(", "))
             }
+            ParseError::InvalidWellValue { mnemonic, value } => match value {
+                Some(value) => format!("'{value}' isn't a valid value for '{mnemonic}' in the Well section"),
+                None => format!("'{mnemonic}' in the Well section has no value"),
+            },
             ParseError::WellDataMissingRequiredValueForMnemonic { mnemonic } => {
-                write!(f, "ParseError::WellDataMissingRequiredValueForMnemonic({:?})", mnemonic)
+                format!("'{mnemonic}' is required in the Well section")
             }
             ParseError::DelimetedValueContainsInvalidChars {
                 key,
                 line_number,
                 invalid_chars,
-                line,
+                ..
             } => {
-                write!(
-                    f,
-                    "ParseError::DelimetedValueContainsInvalidChars(key={:?}, line_number={}, invalid_chars={:?}, line={:?})",
-                    key, line_number, invalid_chars, line
+                let chars: String = invalid_chars.iter().collect();
+                with_line(
+                    format!("'{key}' contains characters that aren't allowed ('{chars}')"),
+                    line_number,
                 )
             }
             ParseError::DuplicateSection {
                 section,
                 line_number,
                 duplicate_line_number,
-            } => {
-                write!(
-                    f,
-                    "ParseError::DuplicateSection(section={:?}, line_number={}, duplicate_line_number={})",
-                    section, line_number, duplicate_line_number
-                )
-            }
-            ParseError::VersionInformationNotFirst { line_number } => {
-                write!(f, "ParseError::VersionInformationNotFirst(line_number={})", line_number)
-            }
-            ParseError::AsciiLogDataSectionNotLast { line_number } => {
-                write!(f, "ParseError::AsciiLogDataSectionNotLast(line_number={})", line_number)
-            }
+            } => with_line(
+                format!("the {section:?} section was already defined on line {line_number}"),
+                duplicate_line_number,
+            ),
+            ParseError::VersionInformationNotFirst { line_number } => with_line(
+                "the Version section has to be the first section".to_string(),
+                line_number,
+            ),
+            ParseError::AsciiLogDataSectionNotLast { line_number } => with_line(
+                "the ASCII log data (~A) section has to be the last section".to_string(),
+                line_number,
+            ),
             ParseError::InvalidAsciiValue { raw_value, line_number } => {
-                write!(
-                    f,
-                    "ParseError::InvalidAsciiValue(raw_value={:?}, line_number={})",
-                    raw_value, line_number
-                )
-            }
-            ParseError::InvalidAsciiFloatValue { raw_value } => {
-                write!(f, "ParseError::InvalidAsciiFloatValue({:?})", raw_value)
+                with_line(format!("'{raw_value}' isn't a valid value"), line_number)
             }
+            ParseError::InvalidAsciiFloatValue { raw_value } => format!("'{raw_value}' isn't a number"),
             ParseError::AsciiColumnsMismatch {
                 line_number,
                 num_cols_in_headers,
                 num_cols_in_row,
-            } => {
-                write!(
-                    f,
-                    "ParseError::AsciiColumnsMismatch(line_number={}, headers={}, row={})",
-                    line_number, num_cols_in_headers, num_cols_in_row
-                )
-            }
+            } => with_line(
+                format!("row has {num_cols_in_row} values, expected {num_cols_in_headers}"),
+                line_number,
+            ),
             ParseError::AsciiDataContainsInvalidLine { line_number, line_kind } => {
-                write!(
-                    f,
-                    "ParseError::AsciiDataContainsInvalidLine(line_number={}, line_kind={:?})",
-                    line_number, line_kind
-                )
+                let kind = match line_kind {
+                    InvalidLineKind::Empty => "empty lines",
+                    InvalidLineKind::Comment => "comments",
+                };
+                with_line(format!("ASCII log data can't contain {kind}"), line_number)
             }
             ParseError::CurvesAndAsciiDataColumnsMismatch {
                 num_curves,
                 num_data_cols,
                 curves_line_number,
                 ascii_data_line_number,
-            } => {
-                write!(
-                    f,
-                    "ParseError::CurvesAndAsciiDataColumnsMismatch(num_curves={}, num_data_cols={}, curves_line_number={}, ascii_data_line_number={})",
-                    num_curves, num_data_cols, curves_line_number, ascii_data_line_number
+            } => format!(
+                "the Curve section (line {curves_line_number}) has {num_curves} curves, but ASCII log data (line {ascii_data_line_number}) has {num_data_cols} columns"
+            ),
+            ParseError::ConvertingTo { format } => format!("unable to convert to {format}"),
+            ParseError::DisallowedFirstCurve { got, expected_one_of } => format!(
+                "the first curve has to be the index, one of {}, found '{got}'",
+                expected_one_of.join(", ")
+            ),
+            ParseError::UnrecognizedSection { name, line_number } => {
+                with_line(format!("unrecognized section '{name}'"), line_number)
+            }
+            ParseError::InconsistentStep { start, stop, step } => {
+                format!(
+                    "STEP {} can't get from STRT {} to STOP {}",
+                    number(*step),
+                    number(*start),
+                    number(*stop)
                 )
             }
-            ParseError::ConvertingTo { format } => {
-                write!(f, "ParseError::ConvertingTo({:?})", format)
+            ParseError::UnknownUnit { unit } => format!("unknown unit '{unit}'"),
+            ParseError::MissingCurve { mnemonic } => format!("there is no curve with mnemonic '{mnemonic}'"),
+            ParseError::IncompatibleUnits { from, to } => format!("can't convert '{from}' to '{to}'"),
+            ParseError::DepthMismatch {
+                mnemonic,
+                expected,
+                found,
+            } => format!(
+                "{mnemonic} is {}, but the matching depth in ASCII log data is {}",
+                number(*expected),
+                number(*found)
+            ),
+            ParseError::InconsistentDepthStep { step, found, depth } => {
+                format!(
+                    "depth {} is {} from the one before it, STEP is {}",
+                    number(*depth),
+                    number(*found),
+                    number(*step)
+                )
             }
-            ParseError::DisallowedFirstCurve { got, expected_one_of } => {
-                write!(
-                    f,
-                    "ParseError::DisallowedFirstCurve(got={:?}, expected_one_of={:?})",
-                    got, expected_one_of
+            ParseError::NonMonotonicIndex { previous, depth } => {
+                format!(
+                    "depth {} goes back the other way after {}",
+                    number(*depth),
+                    number(*previous)
                 )
             }
-        }
+            ParseError::UndeclaredStep { step } => {
+                format!("STEP is 0, but every depth is {} apart", number(*step))
+            }
+            ParseError::MismatchedRuns {
+                run,
+                field,
+                expected,
+                found,
+            } => format!("run '{run}' has {field} '{found}', expected '{expected}'"),
+        };
+        f.write_str(&message)
     }
 }
+
+// Rounded, so float error like 0.11500000000000909 doesn't end up in a description.
+fn number(value: f64) -> String {
+    let rounded = format!("{value:.6}");
+    rounded.trim_end_matches('0').trim_end_matches('.').to_string()
+}
@@ -0,0 +1,300 @@
+use crate::{
+    DEFAULT_WRAP_WIDTH, Delimiter, Encoding, LasFile, LasWriter, LasWriterOptions, ParseError,
+    sections::{Cell, columns_to_rows},
+    write_comments,
+    writer::{IoWriter, write_header, write_las, write_row, write_section_groups, write_sections_before_log_data},
+};
+use serde::{
+    Deserializer,
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::{BufReader, BufWriter, Read, Write},
+};
+
+impl<W> LasWriter<W>
+where
+    W: Write,
+{
+    /// Writes JSON from [`crate::JsonSink`] or [`LasFile::to_json_str`] as a .las file, without building a [`LasFile`].
+    ///
+    /// Rows of ASCII log data are written as they are read, as long as "AsciiLogData" comes after the Version, Well
+    /// and Curve sections and its own "header" comes before its "rows", which is how [`crate::JsonSink`] writes them.
+    /// Otherwise (eg. columnar JSON) they are held onto until the end. [`LasWriterOptions::align_columns`] only applies
+    /// to rows that were held onto. We wrap your reader in [`BufReader`].
+    pub fn write_json<R>(&mut self, reader: R) -> Result<(), ParseError>
+    where
+        R: Read,
+    {
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(reader));
+        self.write_deserialized(|visitor| {
+            deserializer.deserialize_map(visitor)?;
+            deserializer.end()
+        })
+    }
+
+    /// Writes YAML from [`crate::YamlSink`] or [`LasFile::to_yaml_str`] as a .las file, without building a [`LasFile`].
+    /// See [`LasWriter::write_json`].
+    pub fn write_yaml<R>(&mut self, reader: R) -> Result<(), ParseError>
+    where
+        R: Read,
+    {
+        let deserializer = serde_yaml_ng::Deserializer::from_reader(BufReader::new(reader));
+        self.write_deserialized(|visitor| deserializer.deserialize_map(visitor))
+    }
+
+    fn write_deserialized<F, E>(&mut self, deserialize: F) -> Result<(), ParseError>
+    where
+        F: FnOnce(LasVisitor<'_, IoWriter<'_, BufWriter<W>>>) -> Result<(), E>,
+        E: fmt::Display,
+    {
+        let mut message = None;
+        let result = self.write_with(Encoding::Auto, |out, options| {
+            deserialize(LasVisitor { out, options }).map_err(|e| {
+                message = Some(e.to_string());
+                fmt::Error
+            })
+        });
+        match (result, message) {
+            (Err(ParseError::Io(e)), _) => Err(ParseError::Io(e)),
+            (Err(_), Some(message)) => Err(ParseError::Error { message }),
+            (result, _) => result,
+        }
+    }
+}
+
+fn write_error<E: de::Error>(_: fmt::Error) -> E {
+    E::custom("Unable to write .las file")
+}
+
+// Visits the top level object, writing each part of the .las file as soon as it can.
+struct LasVisitor<'a, O> {
+    out: &'a mut O,
+    options: &'a LasWriterOptions,
+}
+
+impl<'de, O> Visitor<'de> for LasVisitor<'_, O>
+where
+    O: fmt::Write,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a LAS file, as a map of sections")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut las_file = LasFile::default();
+        // Version, Well and Curve sections, which have to be written before "~A".
+        let mut seen = [false; 3];
+        let mut streamed = false;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "VersionInformation" => {
+                    las_file.version_information = map.next_value()?;
+                    seen[0] = true;
+                }
+                "WellInformation" => {
+                    las_file.well_information = map.next_value()?;
+                    seen[1] = true;
+                }
+                "CurveInformation" => {
+                    las_file.curve_information = map.next_value()?;
+                    seen[2] = true;
+                }
+                "ParameterInformation" | "OtherInformation" if streamed => {
+                    return Err(de::Error::custom(format!("'{key}' has to come before 'AsciiLogData'")));
+                }
+                "ParameterInformation" => las_file.parameter_information = map.next_value()?,
+                "OtherInformation" => las_file.other_information = map.next_value()?,
+                "SectionGroups" => las_file.section_groups = map.next_value()?,
+                "AsciiLogData" if seen.iter().all(|&s| s) && !streamed => {
+                    write_sections_before_log_data(self.out, &las_file, self.options).map_err(write_error)?;
+                    let version = &las_file.version_information;
+                    map.next_value_seed(LogDataSeed {
+                        out: &mut *self.out,
+                        options: self.options,
+                        delimiter: version.delimiter(),
+                        wrap_width: version.is_wrapped().then_some(DEFAULT_WRAP_WIDTH),
+                        null: null_text(&las_file),
+                    })?;
+                    streamed = true;
+                }
+                "AsciiLogData" => las_file.ascii_log_data = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        if streamed {
+            return write_section_groups(self.out, &las_file, self.options).map_err(write_error);
+        }
+        las_file.fill_null_cells();
+        las_file.apply_null_value();
+        write_las(self.out, &las_file, self.options).map_err(write_error)
+    }
+}
+
+// Text of the Well section "NULL", for values that are null in JSON or YAML.
+fn null_text(las_file: &LasFile) -> String {
+    las_file
+        .well_information
+        .null
+        .value
+        .as_ref()
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+// Writes "AsciiLogData" as it is read, rows included.
+struct LogDataSeed<'a, O> {
+    out: &'a mut O,
+    options: &'a LasWriterOptions,
+    delimiter: Delimiter,
+    wrap_width: Option<usize>,
+    null: String,
+}
+
+impl<O> LogDataSeed<'_, O>
+where
+    O: fmt::Write,
+{
+    fn write_row(&mut self, row: &[String]) -> fmt::Result {
+        write_row(self.out, row, self.delimiter, self.wrap_width, &[], self.options)
+    }
+}
+
+impl<'de, O> DeserializeSeed<'de> for LogDataSeed<'_, O>
+where
+    O: fmt::Write,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, O> Visitor<'de> for LogDataSeed<'_, O>
+where
+    O: fmt::Write,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ASCII log data")
+    }
+
+    fn visit_map<A>(mut self, mut map: A) -> Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut headers: Vec<String> = vec![];
+        let mut comments: Option<Vec<String>> = None;
+        let mut header: Option<String> = None;
+        let mut wrote_header = false;
+        // Rows we can't write yet, because they came before "header" or are columnar.
+        let mut pending: Vec<Vec<String>> = vec![];
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "headers" => headers = map.next_value::<Option<Vec<String>>>()?.unwrap_or_default(),
+                "comments" => comments = map.next_value()?,
+                "header" => header = Some(map.next_value()?),
+                "rows" => {
+                    if let Some(header) = header.as_ref().filter(|_| !wrote_header) {
+                        write_comments(self.out, &comments).map_err(write_error)?;
+                        write_header(self.out, header, self.options).map_err(write_error)?;
+                        wrote_header = true;
+                    }
+                    map.next_value_seed(RowsSeed {
+                        log_data: &mut self,
+                        pending: (!wrote_header).then_some(&mut pending),
+                    })?;
+                }
+                "columns" => {
+                    let columns: HashMap<String, Vec<Cell>> = map.next_value()?;
+                    for mut row in columns_to_rows(&headers, columns) {
+                        fill_nulls(&mut row, &self.null);
+                        pending.push(row);
+                    }
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        if !wrote_header {
+            let header = header.ok_or_else(|| de::Error::missing_field("header"))?;
+            write_comments(self.out, &comments).map_err(write_error)?;
+            write_header(self.out, &header, self.options).map_err(write_error)?;
+        }
+        for row in pending.iter() {
+            self.write_row(row).map_err(write_error)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes each row as it is read, or holds onto it in `pending`.
+struct RowsSeed<'a, 'b, O> {
+    log_data: &'a mut LogDataSeed<'b, O>,
+    pending: Option<&'a mut Vec<Vec<String>>>,
+}
+
+impl<'de, O> DeserializeSeed<'de> for RowsSeed<'_, '_, O>
+where
+    O: fmt::Write,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, O> Visitor<'de> for RowsSeed<'_, '_, O>
+where
+    O: fmt::Write,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rows of ASCII log data")
+    }
+
+    fn visit_seq<A>(mut self, mut seq: A) -> Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(row) = seq.next_element::<Vec<Cell>>()? {
+            let mut row: Vec<String> = row.into_iter().map(|cell| cell.0).collect();
+            fill_nulls(&mut row, &self.log_data.null);
+            match self.pending.as_mut() {
+                Some(pending) => pending.push(row),
+                None => self.log_data.write_row(&row).map_err(write_error)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn fill_nulls(row: &mut [String], null: &str) {
+    for cell in row.iter_mut().filter(|cell| cell.is_empty()) {
+        *cell = null.to_string();
+    }
+}
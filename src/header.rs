@@ -0,0 +1,168 @@
+use crate::{
+    Diagnostic, Encoding, LasFile, ParseError, ParseOptions,
+    parse::{AstSink, LasParser},
+    sections::{
+        CurveInformation, OtherInformation, ParameterInformation, SectionGroup, VersionInformation, WellInformation,
+    },
+    tokenizer::LasTokenizer,
+};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+};
+
+// How much of the end of a file is read at a time, looking for the last row of ASCII log data.
+const TAIL_CHUNK_SIZE: u64 = 4096;
+
+/// Every section of a .las file before "~A", read by [`parse_header`] without touching ASCII log data.
+#[derive(Debug, Default, Serialize)]
+pub struct LasHeader {
+    #[serde(rename = "VersionInformation")]
+    pub version_information: VersionInformation,
+
+    #[serde(rename = "WellInformation")]
+    pub well_information: WellInformation,
+
+    #[serde(rename = "CurveInformation")]
+    pub curve_information: CurveInformation,
+
+    #[serde(rename = "OtherInformation")]
+    pub other_information: Option<OtherInformation>,
+
+    #[serde(rename = "ParameterInformation")]
+    pub parameter_information: Option<ParameterInformation>,
+
+    // LAS 3.0 section groups found before log data.
+    #[serde(rename = "SectionGroups", skip_serializing_if = "Vec::is_empty")]
+    pub section_groups: Vec<SectionGroup>,
+
+    /// Byte offset of the line after "~A", where ASCII log data starts. None when there is no "~A" section.
+    #[serde(rename = "DataOffset")]
+    pub data_offset: Option<u64>,
+
+    #[serde(skip)]
+    pub encoding: Encoding,
+}
+
+impl LasHeader {
+    /// Counts rows of ASCII log data by scanning lines from [`LasHeader::data_offset`], without parsing them.
+    /// Blank lines and comments aren't counted. None for wrapped files, where a row spans several lines.
+    pub fn count_rows<R>(&self, mut reader: R) -> Result<Option<usize>, ParseError>
+    where
+        R: Read + Seek,
+    {
+        let Some(data_offset) = self.data_offset.filter(|_| !self.version_information.is_wrapped()) else {
+            return Ok(None);
+        };
+        reader.seek(SeekFrom::Start(data_offset))?;
+
+        let mut rows = 0;
+        // Whether we are still looking for the first character of a line.
+        let mut line_start = true;
+        let mut buffer = vec![0; 64 * 1024];
+        loop {
+            let len = reader.read(&mut buffer)?;
+            if len == 0 {
+                return Ok(Some(rows));
+            }
+            for &byte in buffer[..len].iter() {
+                match byte {
+                    b'\n' => line_start = true,
+                    b' ' | b'\t' | b'\r' => {}
+                    // A LAS 3.0 section after log data.
+                    b'~' if line_start => return Ok(Some(rows)),
+                    _ if line_start => {
+                        line_start = false;
+                        if byte != b'#' {
+                            rows += 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    /// Index (eg. depth) of the last row of ASCII log data, read from the end of the file.
+    /// None for wrapped and LAS 3.0 files, where the last line doesn't have to start with the index.
+    pub fn last_index<R>(&self, mut reader: R) -> Result<Option<f64>, ParseError>
+    where
+        R: Read + Seek,
+    {
+        let version = &self.version_information;
+        let Some(data_offset) = self.data_offset.filter(|_| !version.is_wrapped() && !version.is_las3()) else {
+            return Ok(None);
+        };
+
+        let mut position = reader.seek(SeekFrom::End(0))?;
+        let mut tail = vec![];
+        while position > data_offset {
+            let len = TAIL_CHUNK_SIZE.min(position - data_offset);
+            position -= len;
+            reader.seek(SeekFrom::Start(position))?;
+            let mut chunk = vec![0; len as usize];
+            reader.read_exact(&mut chunk)?;
+            chunk.extend_from_slice(&tail);
+            tail = chunk;
+
+            if let Some(line) = last_row(&tail, position == data_offset) {
+                let index = version.delimiter().split(&line).into_iter().next();
+                return Ok(index.and_then(|i| i.parse::<f64>().ok()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Reads the Version, Well, Curve and Parameter (and Other) sections of a .las file, stopping at "~A".
+/// Much faster than [`crate::parse`] for building a catalogue, as no ASCII log data is read.
+pub fn parse_header(las_file_path: &str) -> Result<LasHeader, ParseError> {
+    parse_header_with_options(las_file_path, ParseOptions::default()).map(|(header, _)| header)
+}
+
+/// Same as [`parse_header`] using `options`, see [`crate::parse_with_options`].
+pub fn parse_header_with_options(
+    las_file_path: &str,
+    options: ParseOptions,
+) -> Result<(LasHeader, Vec<Diagnostic>), ParseError> {
+    let options = ParseOptions {
+        stop_before_data: true,
+        ..options
+    };
+    let reader = BufReader::new(File::open(las_file_path)?);
+    let tokenizer = LasTokenizer::with_encoding(reader, options.encoding);
+    let mut parser = LasParser::with_options(tokenizer, options);
+    let mut sink = AstSink::new();
+
+    parser.parse_into(&mut sink)?;
+    let diagnostics = parser.take_diagnostics();
+    let las_file = LasFile::try_from(sink)?;
+    let header = LasHeader {
+        version_information: las_file.version_information,
+        well_information: las_file.well_information,
+        curve_information: las_file.curve_information,
+        other_information: las_file.other_information,
+        parameter_information: las_file.parameter_information,
+        section_groups: las_file.section_groups,
+        data_offset: parser.stopped_before_data().then(|| parser.tokens().bytes_read()),
+        encoding: parser.tokens().encoding(),
+    };
+    Ok((header, diagnostics))
+}
+
+// Last line of `bytes` that isn't blank or a comment. The first line is only complete when `at_start`.
+fn last_row(bytes: &[u8], at_start: bool) -> Option<String> {
+    let lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+    for (i, line) in lines.iter().enumerate().rev() {
+        if i == 0 && !at_start {
+            break;
+        }
+        let text = String::from_utf8_lossy(line);
+        let text = text.trim();
+        if !text.is_empty() && !text.starts_with('#') {
+            return Some(text.to_string());
+        }
+    }
+    None
+}
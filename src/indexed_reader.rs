@@ -0,0 +1,235 @@
+use crate::{LasFile, LasHeader, ParseError, ParseOptions, SectionKind, parse_from_with_options, parse_header};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+#[derive(Debug, Clone)]
+pub struct IndexOptions {
+    /// Every `interval`th row is indexed. Smaller intervals mean less data parsed per query and a bigger index.
+    pub interval: usize,
+    /// Where to persist the index, eg. "big.las.idx". It is loaded from there when it is still up to date with
+    /// the .las file, otherwise it is built and written there if possible, eg. not in a read only directory.
+    /// Not persisted when None.
+    pub index_path: Option<PathBuf>,
+}
+
+impl Default for IndexOptions {
+    fn default() -> Self {
+        Self {
+            interval: 1000,
+            index_path: None,
+        }
+    }
+}
+
+/// Sparse index of where rows of ASCII log data start in a .las file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LasIndex {
+    pub interval: usize,
+    pub num_rows: usize,
+    /// Index (eg. depth) and byte offset of every `interval`th row, starting with the first one.
+    pub entries: Vec<(f64, u64)>,
+    // Size and modification time (milliseconds since the epoch) of the indexed file, to tell when it is stale.
+    file_len: u64,
+    modified: Option<u64>,
+}
+
+/// Reads rows of ASCII log data from anywhere in a (huge) .las file, without streaming from the top.
+///
+/// Opening a file reads its header and indexes the "~A" section once, see [`IndexOptions`]. Each query then
+/// seeks to the closest indexed row and only parses the lines it needs.
+pub struct IndexedLasReader {
+    path: PathBuf,
+    header: LasHeader,
+    index: LasIndex,
+}
+
+impl IndexedLasReader {
+    pub fn open(las_file_path: &str) -> Result<Self, ParseError> {
+        Self::open_with_options(las_file_path, IndexOptions::default())
+    }
+
+    pub fn open_with_options(las_file_path: &str, options: IndexOptions) -> Result<Self, ParseError> {
+        let path = PathBuf::from(las_file_path);
+        let header = parse_header(las_file_path)?;
+        let data_offset = header.data_offset.ok_or(ParseError::MissingSection {
+            section: SectionKind::AsciiLogData,
+        })?;
+        let (file_len, modified) = file_stamp(&path)?;
+
+        let persisted = options
+            .index_path
+            .as_ref()
+            .and_then(|index_path| fs::read_to_string(index_path).ok())
+            .and_then(|json| serde_json::from_str::<LasIndex>(&json).ok())
+            .filter(|index| {
+                index.file_len == file_len && index.modified == modified && index.interval == options.interval.max(1)
+            });
+        let index = match persisted {
+            Some(index) => index,
+            None => {
+                let mut index = build_index(&path, &header, data_offset, options.interval.max(1))?;
+                (index.file_len, index.modified) = (file_len, modified);
+                // Only saves building it again next time, so the file can still be read without it.
+                if let (Some(index_path), Ok(json)) = (options.index_path.as_ref(), serde_json::to_string(&index)) {
+                    let _ = fs::write(index_path, json);
+                }
+                index
+            }
+        };
+        Ok(Self { path, header, index })
+    }
+
+    pub fn header(&self) -> &LasHeader {
+        &self.header
+    }
+
+    pub fn index(&self) -> &LasIndex {
+        &self.index
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.index.num_rows
+    }
+
+    /// Rows from `top` to `base` (inclusive, either way round), along with every header section.
+    /// Same as [`LasFile::slice_depth`] on the whole file, only the rows around the window are parsed.
+    pub fn read_range(&self, top: f64, base: f64) -> Result<LasFile, ParseError> {
+        let (low, high) = (top.min(base), top.max(base));
+        let entries = &self.index.entries;
+        let increasing = entries.first().map(|e| e.0) <= entries.last().map(|e| e.0);
+        // Last indexed row before the window, and first indexed row after it.
+        let (start, end) = match increasing {
+            true => (
+                entries.partition_point(|e| e.0 <= low),
+                entries.partition_point(|e| e.0 <= high),
+            ),
+            false => (
+                entries.partition_point(|e| e.0 >= high),
+                entries.partition_point(|e| e.0 >= low),
+            ),
+        };
+        let mut las_file = self.read_rows(start.saturating_sub(1), end)?;
+        las_file.slice_depth(top, base)?;
+        Ok(las_file)
+    }
+
+    /// Row `n` of ASCII log data (0 based), as written in the file.
+    pub fn row(&self, n: usize) -> Result<Option<Vec<String>>, ParseError> {
+        if n >= self.index.num_rows {
+            return Ok(None);
+        }
+        let entry = n / self.index.interval;
+        let las_file = self.read_rows(entry, entry + 1)?;
+        let row = las_file.ascii_log_data.row(n % self.index.interval);
+        Ok(row.map(|values| values.into_iter().map(str::to_string).collect()))
+    }
+
+    // Parses the rows from index entry `start` up to (not including) entry `end`. The header is parsed again
+    // in front of them, so rows go through the same parser as everything else.
+    fn read_rows(&self, start: usize, end: usize) -> Result<LasFile, ParseError> {
+        let data_offset = self.header.data_offset.unwrap_or_default();
+        let entries = &self.index.entries;
+        let from = entries.get(start).map_or(data_offset, |e| e.1);
+        let to = entries.get(end).map_or(u64::MAX, |e| e.1);
+
+        let head = File::open(&self.path)?.take(data_offset);
+        let mut rows = File::open(&self.path)?;
+        rows.seek(SeekFrom::Start(from))?;
+        let options = ParseOptions {
+            encoding: self.header.encoding,
+            ..Default::default()
+        };
+        let (las_file, _) = parse_from_with_options(head.chain(rows.take(to.saturating_sub(from))), options)?;
+        Ok(las_file)
+    }
+}
+
+// Scans "~A" line by line, noting where every `interval`th row starts.
+fn build_index(path: &Path, header: &LasHeader, data_offset: u64, interval: usize) -> Result<LasIndex, ParseError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    reader.seek(SeekFrom::Start(data_offset))?;
+    let delimiter = header.version_information.delimiter();
+    let wrapped = header.version_information.is_wrapped();
+    let num_curves = header.curve_information.curves.len();
+
+    let mut entries: Vec<(f64, u64)> = vec![];
+    let mut num_rows = 0;
+    let mut previous: Option<f64> = None;
+    // Sign of the first step between two different depths.
+    let mut direction = 0.0;
+    // Values of a wrapped row read so far, a new row starts at 0.
+    let mut num_values = 0;
+    let mut offset = data_offset;
+    let mut line = vec![];
+    loop {
+        line.clear();
+        let len = reader.read_until(b'\n', &mut line)?;
+        if len == 0 {
+            break;
+        }
+        let line_offset = offset;
+        offset += len as u64;
+
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        // A LAS 3.0 section after log data.
+        if text.starts_with('~') {
+            break;
+        }
+
+        if num_values == 0 {
+            let raw = delimiter.split(text).into_iter().next().unwrap_or_default();
+            let depth = raw
+                .parse::<f64>()
+                .map_err(|_| ParseError::InvalidAsciiFloatValue { raw_value: raw.clone() })?;
+            // Queries binary search the index and crop what they read, so every row has to move one way,
+            // not only the indexed ones.
+            if let Some(previous) = previous {
+                let step = depth - previous;
+                if step * direction < 0.0 {
+                    return Err(ParseError::NonMonotonicIndex { previous, depth });
+                }
+                if step != 0.0 {
+                    direction = step;
+                }
+            }
+            previous = Some(depth);
+            if num_rows % interval == 0 {
+                entries.push((depth, line_offset));
+            }
+            num_rows += 1;
+        }
+        if wrapped {
+            num_values += delimiter.split(text).len();
+            if num_values >= num_curves {
+                num_values = 0;
+            }
+        }
+    }
+
+    Ok(LasIndex {
+        interval,
+        num_rows,
+        entries,
+        file_len: 0,
+        modified: None,
+    })
+}
+
+fn file_stamp(path: &Path) -> Result<(u64, Option<u64>), ParseError> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64);
+    Ok((metadata.len(), modified))
+}
@@ -1,11 +1,14 @@
 use crate::{
-    ParseError,
+    Encoding, ParseError,
     parse::{AstSink, SectionKind},
     sections::*,
 };
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+/// Maximum line length for wrapped ASCII log data, per CWLS v2.0.
+pub const DEFAULT_WRAP_WIDTH: usize = 80;
+
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub struct LasFile {
     #[serde(rename = "VersionInformation")]
@@ -25,20 +28,34 @@ pub struct LasFile {
 
     #[serde(rename = "ParameterInformation")]
     pub parameter_information: Option<ParameterInformation>,
+
+    // LAS 3.0 section groups other than "Log", eg. "Core", "Tops" or "Inclinometry".
+    #[serde(rename = "SectionGroups", default, skip_serializing_if = "Vec::is_empty")]
+    pub section_groups: Vec<SectionGroup>,
+
+    /// Encoding the file was read in. [`Encoding::Auto`] for UTF-8 files read with [`Encoding::Auto`], and files
+    /// that weren't read from a .las file, eg. built in code or from JSON. [`crate::LasWriter`] writes it back
+    /// in this encoding (UTF-8 for [`Encoding::Auto`]), unless told otherwise.
+    #[serde(skip)]
+    pub encoding: Encoding,
 }
 
 impl fmt::Display for LasFile {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.version_information)?;
-        write!(f, "{}", self.well_information)?;
-        write!(f, "{}", self.curve_information)?;
-        if let Some(parameter) = self.parameter_information.as_ref() {
-            write!(f, "{parameter}")?;
-        }
-        if let Some(other) = self.other_information.as_ref() {
-            write!(f, "{other}")?;
-        }
-        write!(f, "{}", self.ascii_log_data)
+        let wrap_width = self.version_information.is_wrapped().then_some(DEFAULT_WRAP_WIDTH);
+        self.write_las(f, wrap_width)
+    }
+}
+
+// Lets us format a LasFile with wrapping that doesn't agree with its "WRAP" line.
+struct WrappedLasFile<'a> {
+    las_file: &'a LasFile,
+    line_width: usize,
+}
+
+impl fmt::Display for WrappedLasFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.las_file.write_las(f, Some(self.line_width))
     }
 }
 
@@ -51,22 +68,87 @@ impl LasFile {
         other_info: Option<OtherInformation>,
         param_info: Option<ParameterInformation>,
     ) -> Self {
-        Self {
+        let mut las_file = Self {
             version_information: version_info,
             well_information: well_info,
             curve_information: curve_info,
             ascii_log_data,
             other_information: other_info,
             parameter_information: param_info,
+            section_groups: vec![],
+            encoding: Encoding::Auto,
+        };
+        las_file.apply_null_value();
+        las_file
+    }
+
+    /// Curve (column of ASCII log data) by mnemonic (case insensitive).
+    pub fn curve(&self, mnemonic: &str) -> Option<&Curve> {
+        self.ascii_log_data.curve(mnemonic)
+    }
+
+    /// The index curve ("DEPT", "DEPTH", "TIME" or "INDEX"), which is always the first curve.
+    pub fn index(&self) -> Option<&Curve> {
+        self.ascii_log_data.index()
+    }
+
+    // ASCII log data that was null in JSON or YAML comes through empty, it is "NULL" from the Well section.
+    pub(crate) fn fill_null_cells(&mut self) {
+        let Some(null) = self.well_information.null.value.as_ref().map(|v| v.to_string()) else {
+            return;
+        };
+        for curve in self.ascii_log_data.curves.iter_mut() {
+            for i in 0..curve.len() {
+                if curve.raw()[i].is_empty() {
+                    curve.set(i, null.clone());
+                }
+            }
+        }
+    }
+
+    // Lets curves know which value is "NULL", from the Well section.
+    pub(crate) fn apply_null_value(&mut self) {
+        let null = self.well_information.null_value();
+        self.ascii_log_data.set_null_value(null);
+        for group in self.section_groups.iter_mut() {
+            for data in group.data.iter_mut() {
+                data.set_null_value(null);
+            }
         }
     }
 
+    /// LAS 3.0 section group by name (case insensitive), eg. "Core" for "~Core_Data".
+    pub fn section_group(&self, name: &str) -> Option<&SectionGroup> {
+        self.section_groups.iter().find(|g| g.name.eq_ignore_ascii_case(name))
+    }
+
+    /// LAS 3.0 "~Core_*" sections.
+    pub fn core(&self) -> Option<&SectionGroup> {
+        self.section_group("Core")
+    }
+
+    /// LAS 3.0 "~Tops_*" sections.
+    pub fn tops(&self) -> Option<&SectionGroup> {
+        self.section_group("Tops")
+    }
+
+    /// LAS 3.0 "~Inclinometry_*" sections.
+    pub fn inclinometry(&self) -> Option<&SectionGroup> {
+        self.section_group("Inclinometry")
+    }
+
     pub fn try_from_json_str(json_str: &str) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(json_str)
+        let mut las_file: Self = serde_json::from_str(json_str)?;
+        las_file.fill_null_cells();
+        las_file.apply_null_value();
+        Ok(las_file)
     }
 
     pub fn try_from_yaml_str(yaml_str: &str) -> Result<Self, serde_yaml_ng::Error> {
-        serde_yaml_ng::from_str(yaml_str)
+        let mut las_file: Self = serde_yaml_ng::from_str(yaml_str)?;
+        las_file.fill_null_cells();
+        las_file.apply_null_value();
+        Ok(las_file)
     }
 
     pub fn to_json_str(&mut self) -> Result<String, ParseError> {
@@ -84,21 +166,57 @@ impl LasFile {
     pub fn to_las_str(&mut self) -> String {
         self.to_string()
     }
+
+    /// Same as [`LasFile::to_las_str`] but ASCII log data is written wrapped ("WRAP. YES"),
+    /// with no data line longer than `line_width` (unless a single value is longer).
+    pub fn to_wrapped_las_str(&self, line_width: usize) -> String {
+        WrappedLasFile {
+            las_file: self,
+            line_width,
+        }
+        .to_string()
+    }
+
+    fn write_las(&self, f: &mut fmt::Formatter<'_>, wrap_width: Option<usize>) -> fmt::Result {
+        self.version_information.write_las(f, wrap_width.is_some())?;
+        write!(f, "{}", self.well_information)?;
+        write!(f, "{}", self.curve_information)?;
+        if let Some(parameter) = self.parameter_information.as_ref() {
+            write!(f, "{parameter}")?;
+        }
+        if let Some(other) = self.other_information.as_ref() {
+            write!(f, "{other}")?;
+        }
+        let delimiter = self.version_information.delimiter();
+        // LAS 3.0 files don't have to contain log data.
+        if !self.ascii_log_data.header.is_empty() {
+            match wrap_width {
+                Some(line_width) => self.ascii_log_data.write_wrapped(f, line_width)?,
+                None => self.ascii_log_data.write_delimited(f, delimiter)?,
+            }
+        }
+        for group in self.section_groups.iter() {
+            group.write_las(f, delimiter)?;
+        }
+        Ok(())
+    }
 }
 
 impl TryFrom<AstSink> for LasFile {
     type Error = ParseError;
 
+    // The parser has already checked the contents of the Version and Well sections,
+    // see `LasParser::validate_header_section`.
     fn try_from(ast_sink: AstSink) -> Result<Self, Self::Error> {
         let mut las_file = LasFile::default();
 
         for section in ast_sink.sections {
             match section.header.kind {
                 SectionKind::Version => {
-                    las_file.version_information = VersionInformation::try_from(section)?;
+                    las_file.version_information = VersionInformation::from_section(section)?;
                 }
                 SectionKind::Well => {
-                    las_file.well_information = WellInformation::try_from(section)?;
+                    las_file.well_information = WellInformation::from_section(section)?;
                 }
                 SectionKind::Curve => {
                     las_file.curve_information = CurveInformation::try_from(section)?;
@@ -112,9 +230,13 @@ impl TryFrom<AstSink> for LasFile {
                 SectionKind::AsciiLogData => {
                     las_file.ascii_log_data = AsciiLogData::try_from(section)?;
                 }
+                SectionKind::GroupParameter | SectionKind::GroupDefinition | SectionKind::GroupData => {
+                    SectionGroup::add_section(&mut las_file.section_groups, section)?;
+                }
             }
         }
 
+        las_file.apply_null_value();
         Ok(las_file)
     }
 }
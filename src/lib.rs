@@ -1,16 +1,53 @@
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+mod batch;
+mod builder;
+mod diagnostics;
+mod document;
+mod encoding;
 mod errors;
+mod from_json;
+mod header;
+mod indexed_reader;
 mod las_file;
+mod merge;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod resample;
+mod subset;
+mod units;
+mod writer;
 
 pub(crate) mod parse;
 pub(crate) mod tokenizer;
 
 pub mod sections;
+#[cfg(feature = "arrow")]
+pub use arrow::*;
+pub use batch::*;
+pub use builder::*;
+pub use diagnostics::*;
+pub use document::*;
+pub use encoding::*;
 pub use errors::*;
+pub use header::*;
+pub use indexed_reader::*;
 pub use las_file::*;
-pub use parse::{DataLine, LasValue};
+pub use merge::*;
+#[cfg(feature = "parallel")]
+pub use parallel::*;
+pub use parse::{
+    CsvOptions, CsvSink, DataLine, Delimiter, FilterOptions, FilterSink, JsonOptions, JsonSink, LasFloat, LasValue,
+    NdjsonSink, ParseOptions, Section, SectionEntry, SectionHeader, SectionKind, Sink, Strictness, YamlSink,
+};
+#[cfg(feature = "parquet")]
+pub use parse::{ParquetOptions, ParquetSink};
+pub use resample::*;
+pub use units::*;
+pub use writer::*;
 
 use crate::{parse::*, tokenizer::LasTokenizer};
 use std::{
@@ -34,10 +71,29 @@ where
             let mut sink = JsonSink::new(writer);
             parser.parse_into(&mut sink)?;
         }
+        OutputFormat::NDJSON => {
+            let mut sink = NdjsonSink::new(writer);
+            parser.parse_into(&mut sink)?;
+        }
         OutputFormat::YAML | OutputFormat::YML => {
             let mut sink = YamlSink::new(writer);
             parser.parse_into(&mut sink)?;
         }
+        OutputFormat::CSV => {
+            let mut sink = CsvSink::new(writer, CsvOptions::default());
+            parser.parse_into(&mut sink)?;
+        }
+        OutputFormat::TSV => {
+            let mut sink = CsvSink::new(writer, CsvOptions::tsv());
+            parser.parse_into(&mut sink)?;
+        }
+        OutputFormat::LAS => {
+            let mut sink = AstSink::new();
+            parser.parse_into(&mut sink)?;
+            let mut las_file = LasFile::try_from(sink)?;
+            las_file.encoding = parser.tokens().encoding();
+            LasWriter::new(writer, LasWriterOptions::default()).write(&las_file)?;
+        }
     }
 
     Ok(())
@@ -58,10 +114,29 @@ where
             let mut sink = JsonSink::new(writer);
             parser.parse_into(&mut sink)?;
         }
+        OutputFormat::NDJSON => {
+            let mut sink = NdjsonSink::new(writer);
+            parser.parse_into(&mut sink)?;
+        }
         OutputFormat::YAML | OutputFormat::YML => {
             let mut sink = YamlSink::new(writer);
             parser.parse_into(&mut sink)?;
         }
+        OutputFormat::CSV => {
+            let mut sink = CsvSink::new(writer, CsvOptions::default());
+            parser.parse_into(&mut sink)?;
+        }
+        OutputFormat::TSV => {
+            let mut sink = CsvSink::new(writer, CsvOptions::tsv());
+            parser.parse_into(&mut sink)?;
+        }
+        OutputFormat::LAS => {
+            let mut sink = AstSink::new();
+            parser.parse_into(&mut sink)?;
+            let mut las_file = LasFile::try_from(sink)?;
+            las_file.encoding = parser.tokens().encoding();
+            LasWriter::new(writer, LasWriterOptions::default()).write(&las_file)?;
+        }
     }
 
     Ok(())
@@ -77,38 +152,117 @@ pub fn parse(las_file_path: &str) -> Result<LasFile, ParseError> {
     let mut sink = AstSink::new();
 
     parser.parse_into(&mut sink)?;
-    LasFile::try_from(sink)
+    let mut las_file = LasFile::try_from(sink)?;
+    las_file.encoding = parser.tokens().encoding();
+    Ok(las_file)
+}
+
+/// Parse .las file into LasFile using `options`.
+/// With [`Strictness::Lenient`] parsing keeps going past problems it can recover from, and
+/// returns them alongside whatever could be parsed. Only I/O errors and unrecoverable problems are returned as `Err`.
+pub fn parse_with_options(
+    las_file_path: &str,
+    options: ParseOptions,
+) -> Result<(LasFile, Vec<Diagnostic>), ParseError> {
+    parse_from_with_options(File::open(las_file_path)?, options)
+}
+
+/// Parse from a Read into LasFile using `options`, see [`parse_with_options`].
+/// We wrap your [`reader`] in [`BufReader`]
+pub fn parse_from_with_options<R>(reader: R, options: ParseOptions) -> Result<(LasFile, Vec<Diagnostic>), ParseError>
+where
+    R: Read,
+{
+    let tokenizer = LasTokenizer::with_encoding(BufReader::new(reader), options.encoding);
+    let mut parser = LasParser::with_options(tokenizer, options);
+    let mut sink = AstSink::new();
+
+    parser.parse_into(&mut sink)?;
+    let diagnostics = parser.take_diagnostics();
+    let mut las_file = LasFile::try_from(sink)?;
+    las_file.encoding = parser.tokens().encoding();
+    Ok((las_file, diagnostics))
+}
+
+/// Stream a LAS file from a Read into your own [`Sink`], without building a [`LasFile`].
+/// We wrap your [`reader`] in [`BufReader`]
+pub fn stream<R, S>(reader: R, sink: &mut S) -> Result<(), ParseError>
+where
+    R: Read,
+    S: Sink,
+{
+    stream_with_options(reader, sink, ParseOptions::default()).map(|_| ())
+}
+
+/// Same as [`stream`], returning the problems recovered from when parsing with [`Strictness::Lenient`].
+pub fn stream_with_options<R, S>(reader: R, sink: &mut S, options: ParseOptions) -> Result<Vec<Diagnostic>, ParseError>
+where
+    R: Read,
+    S: Sink,
+{
+    let tokenizer = LasTokenizer::with_encoding(BufReader::new(reader), options.encoding);
+    let mut parser = LasParser::with_options(tokenizer, options);
+    parser.parse_into(sink)?;
+    Ok(parser.take_diagnostics())
 }
 
 #[derive(Debug, Clone, clap::ValueEnum, PartialEq, Eq)]
 pub enum OutputFormat {
     JSON,
+    /// One JSON object per line : a header record, then one per depth step
+    NDJSON,
     YAML,
     YML,
+    /// ASCII log data only
+    CSV,
+    /// ASCII log data only
+    TSV,
+    /// Same LAS version as the source. Built in memory, the other formats are streamed
+    LAS,
+}
+
+impl OutputFormat {
+    /// File extension, without the dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::JSON => "json",
+            OutputFormat::NDJSON => "ndjson",
+            OutputFormat::YAML => "yaml",
+            OutputFormat::YML => "yml",
+            OutputFormat::CSV => "csv",
+            OutputFormat::TSV => "tsv",
+            OutputFormat::LAS => "las",
+        }
+    }
 }
 
 impl fmt::Display for OutputFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             OutputFormat::JSON => write!(f, "JSON"),
+            OutputFormat::NDJSON => write!(f, "NDJSON"),
             OutputFormat::YAML => write!(f, "YAML"),
             OutputFormat::YML => write!(f, "YML"),
+            OutputFormat::CSV => write!(f, "CSV"),
+            OutputFormat::TSV => write!(f, "TSV"),
+            OutputFormat::LAS => write!(f, "LAS"),
         }
     }
 }
 
-pub(crate) fn any_present<T>(items: &[&Option<T>]) -> bool {
-    items.iter().any(|o| o.is_some())
+// Number of digits after the decimal point, eg. "1670.0000" -> 4
+pub(crate) fn decimal_places(raw: &str) -> usize {
+    match raw.split_once('.') {
+        Some((_, decimals)) => decimals.chars().take_while(char::is_ascii_digit).count(),
+        None => 0,
+    }
 }
 
-pub(crate) fn write_kv_opt(f: &mut fmt::Formatter<'_>, kv: &Option<DataLine>) -> fmt::Result {
-    if let Some(v) = kv {
-        writeln!(f, "{v}")?;
-    }
-    Ok(())
+pub(crate) fn any_present<T>(items: &[&Option<T>]) -> bool {
+    items.iter().any(|o| o.is_some())
 }
 
-pub(crate) fn write_comments(f: &mut fmt::Formatter<'_>, comments: &Option<Vec<String>>) -> fmt::Result {
+pub(crate) fn write_comments(f: &mut impl fmt::Write, comments: &Option<Vec<String>>) -> fmt::Result {
     if let Some(cs) = comments {
         for c in cs {
             let fc = format!("# {c}").trim().to_string();
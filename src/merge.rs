@@ -0,0 +1,326 @@
+use crate::{
+    DataLine, Diagnostic, LasFile, ParseError, ParseOptions, Severity, Strictness, Unit,
+    parse::DEFAULT_NULL,
+    resample::DEPTH_EPSILON,
+    sections::{AsciiLogData, AsciiLogDataParams, OtherInformation, OtherInformationData, OtherInformationParams},
+};
+use std::path::Path;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MergePriority {
+    /// Where runs overlap, keep the run that comes first, eg. a main pass over its repeat.
+    #[default]
+    First,
+    /// Where runs overlap, keep the run that comes last, eg. a deep run that re-logged the bottom of a shallow one.
+    Last,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct MergeOptions {
+    pub priority: MergePriority,
+    /// [`Strictness::Strict`] fails when runs disagree on "UWI", "API" or depth unit, or have a curve in a unit
+    /// we can't convert to the unit of the same curve in an earlier run. [`Strictness::Lenient`] returns a warning
+    /// instead, and converts runs to the depth unit of the first run. Curves in units we can convert are converted.
+    pub strictness: Strictness,
+}
+
+/// One run of a well, along with the name (usually the file name) recorded for it in the merged file.
+#[derive(Debug)]
+pub struct LasRun {
+    pub name: String,
+    pub las_file: LasFile,
+}
+
+impl LasRun {
+    pub fn new(name: &str, las_file: LasFile) -> Self {
+        Self {
+            name: name.to_string(),
+            las_file,
+        }
+    }
+
+    /// Parses a .las file, named after its file name.
+    pub fn open(las_file_path: &str) -> Result<Self, ParseError> {
+        let name = Path::new(las_file_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| las_file_path.to_string());
+        Ok(Self::new(&name, crate::parse(las_file_path)?))
+    }
+}
+
+impl LasFile {
+    /// Merges runs of the same well, eg. a main pass and a repeat, or shallow and deep sections, into one file.
+    ///
+    /// Curves are the union of every run's curves and rows are lined up on depth. Where runs overlap,
+    /// `options.priority` picks the run whose rows are kept. Curves that run doesn't have are filled in from
+    /// other runs with a row at the same depth, otherwise they are NULL.
+    ///
+    /// Every other section comes from the first run. The interval each run supplied is listed in the Other section.
+    pub fn merge(runs: Vec<LasRun>, options: &MergeOptions) -> Result<(LasFile, Vec<Diagnostic>), ParseError> {
+        let mut runs = runs;
+        if runs.is_empty() {
+            return Err(ParseError::Error {
+                message: "There are no runs to merge!".to_string(),
+            });
+        }
+        let mut diagnostics = vec![];
+        check_runs(&mut runs, options, &mut diagnostics)?;
+
+        let order: Vec<usize> = match options.priority {
+            MergePriority::First => (0..runs.len()).collect(),
+            MergePriority::Last => (0..runs.len()).rev().collect(),
+        };
+        let samples: Vec<Vec<(f64, usize)>> = runs.iter().map(|run| sorted_samples(&run.las_file)).collect();
+
+        // (depth, run, row) of every row we keep. A run only supplies depths outside of the runs before it.
+        let mut rows: Vec<(f64, usize, usize)> = vec![];
+        let mut claimed: Vec<(f64, f64)> = vec![];
+        for &run in order.iter() {
+            let (Some(&(top, _)), Some(&(bottom, _))) = (samples[run].first(), samples[run].last()) else {
+                continue;
+            };
+            rows.extend(
+                samples[run]
+                    .iter()
+                    .filter(|&&(depth, _)| {
+                        !claimed
+                            .iter()
+                            .any(|&(t, b)| depth >= t - DEPTH_EPSILON && depth <= b + DEPTH_EPSILON)
+                    })
+                    .map(|&(depth, row)| (depth, run, row)),
+            );
+            claimed.push((top, bottom));
+        }
+        rows.sort_by(|a, b| a.0.total_cmp(&b.0));
+        // Same direction as the first run.
+        let index = runs[0].las_file.index();
+        if index.is_some_and(|i| i.values().first() > i.values().last()) {
+            rows.reverse();
+        }
+
+        let curves = union_curves(&runs);
+        // Position of each merged curve in each run. Every run's index is the merged index.
+        let columns: Vec<Vec<Option<usize>>> = runs
+            .iter()
+            .map(|run| {
+                let run_curves = &run.las_file.curve_information.curves;
+                (0..curves.len())
+                    .map(|c| match c {
+                        0 => Some(0),
+                        _ => run_curves
+                            .iter()
+                            .skip(1)
+                            .position(|rc| rc.mnemonic.eq_ignore_ascii_case(&curves[c].mnemonic))
+                            .map(|p| p + 1),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let null_text = runs[0]
+            .las_file
+            .well_information
+            .null
+            .value
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| DEFAULT_NULL.to_string());
+        let data_rows: Vec<Vec<String>> = rows
+            .iter()
+            .map(|&(depth, run, row)| {
+                (0..curves.len())
+                    .map(|c| {
+                        let value = match columns[run][c] {
+                            Some(column) => cell(&runs[run].las_file, column, row),
+                            None => order.iter().filter(|&&other| other != run).find_map(|&other| {
+                                let column = columns[other][c]?;
+                                cell(&runs[other].las_file, column, row_at(&samples[other], depth)?)
+                            }),
+                        };
+                        value.unwrap_or_else(|| null_text.clone())
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let unit = match curves.first().and_then(|c| c.unit.as_deref()) {
+            Some(unit) if !unit.is_empty() => format!(" {unit}"),
+            _ => String::new(),
+        };
+        let provenance = intervals(&rows, &data_rows)
+            .into_iter()
+            .map(|(run, top, bottom)| OtherInformationData {
+                text: format!("Interval {top} - {bottom}{unit} from {}", runs[run].name),
+                comments: None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut merged = runs.swap_remove(0).las_file;
+        merged.ascii_log_data = AsciiLogData::new(AsciiLogDataParams {
+            headers: curves.iter().map(|c| c.mnemonic.clone()).collect(),
+            rows: data_rows,
+            comments: merged.ascii_log_data.comments.take(),
+            header: std::mem::take(&mut merged.ascii_log_data.header),
+        });
+        merged.curve_information.curves = curves;
+        merged.apply_null_value();
+        merged.update_depth_range();
+
+        let other = merged.other_information.get_or_insert_with(|| {
+            OtherInformation::new(OtherInformationParams {
+                header: "~Other Information".to_string(),
+                ..Default::default()
+            })
+        });
+        other.data.extend(provenance);
+        Ok((merged, diagnostics))
+    }
+}
+
+// Every run has to be of the same well, in the same depth unit as the first run, with curves in the same unit
+// as the same curve in the runs before it.
+fn check_runs(
+    runs: &mut [LasRun],
+    options: &MergeOptions,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<(), ParseError> {
+    let parse_options = ParseOptions {
+        strictness: options.strictness,
+        ..Default::default()
+    };
+    let Some((first, rest)) = runs.split_first_mut() else {
+        return Ok(());
+    };
+    let first_well = &first.las_file.well_information;
+    let first_unit = depth_unit(&first.las_file);
+    let mut curves = first.las_file.curve_information.curves.clone();
+
+    for run in rest.iter_mut() {
+        let well = &run.las_file.well_information;
+        for (field, expected, found) in [("UWI", &first_well.uwi, &well.uwi), ("API", &first_well.api, &well.api)] {
+            if let (Some(expected), Some(found)) = (value_text(expected), value_text(found))
+                && !expected.eq_ignore_ascii_case(&found)
+            {
+                let rule = mismatch(run, field, expected, found);
+                parse_options.recover(diagnostics, Diagnostic::new(Severity::Warning, rule))?;
+            }
+        }
+
+        let unit = depth_unit(&run.las_file);
+        if !same_unit(&first_unit, &unit) {
+            let rule = mismatch(run, "depth unit", first_unit.clone(), unit);
+            parse_options.recover(diagnostics, Diagnostic::new(Severity::Warning, rule))?;
+            run.las_file.convert_depth_units(&first_unit)?;
+        }
+
+        // Values are spliced together by mnemonic, so they have to be in the same unit.
+        for curve in run.las_file.curve_information.curves.clone().iter().skip(1) {
+            let Some(expected) = curves
+                .iter()
+                .skip(1)
+                .find(|c| c.mnemonic.eq_ignore_ascii_case(&curve.mnemonic))
+            else {
+                curves.push(curve.clone());
+                continue;
+            };
+            let expected_unit = expected.unit.clone().unwrap_or_default();
+            let unit = curve.unit.clone().unwrap_or_default();
+            if same_unit(&expected_unit, &unit) {
+                continue;
+            }
+            match run.las_file.convert_units(&curve.mnemonic, &expected_unit) {
+                Ok(()) => {}
+                Err(ParseError::UnknownUnit { .. } | ParseError::IncompatibleUnits { .. }) => {
+                    let rule = mismatch(run, &format!("{} unit", curve.mnemonic), expected_unit, unit);
+                    parse_options.recover(diagnostics, Diagnostic::new(Severity::Warning, rule))?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn mismatch(run: &LasRun, field: &str, expected: String, found: String) -> ParseError {
+    ParseError::MismatchedRuns {
+        run: run.name.clone(),
+        field: field.to_string(),
+        expected,
+        found,
+    }
+}
+
+fn value_text(line: &Option<DataLine>) -> Option<String> {
+    let value = line.as_ref()?.value.as_ref()?.to_string();
+    (!value.trim().is_empty()).then(|| value.trim().to_string())
+}
+
+fn depth_unit(las_file: &LasFile) -> String {
+    las_file
+        .curve_information
+        .curves
+        .first()
+        .and_then(|c| c.unit.clone())
+        .unwrap_or_default()
+}
+
+// "F" and "FT" are the same unit.
+fn same_unit(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+        || matches!((Unit::find(a), Unit::find(b)), (Some(a), Some(b)) if a.symbol == b.symbol)
+}
+
+// Curves of the first run, followed by any curves only found in later runs.
+fn union_curves(runs: &[LasRun]) -> Vec<DataLine> {
+    let mut curves: Vec<DataLine> = vec![];
+    for run in runs.iter() {
+        for (i, curve) in run.las_file.curve_information.curves.iter().enumerate() {
+            let is_other_index = i == 0 && !curves.is_empty();
+            if !is_other_index && !curves.iter().any(|c| c.mnemonic.eq_ignore_ascii_case(&curve.mnemonic)) {
+                curves.push(curve.clone());
+            }
+        }
+    }
+    curves
+}
+
+// (depth, row) sorted by depth, skipping rows without a depth.
+fn sorted_samples(las_file: &LasFile) -> Vec<(f64, usize)> {
+    let Some(index) = las_file.index() else {
+        return vec![];
+    };
+    let mut samples: Vec<(f64, usize)> = index.iter().enumerate().filter_map(|(i, d)| Some((d?, i))).collect();
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+    samples
+}
+
+// Row of `samples` at `depth`, if there is one.
+fn row_at(samples: &[(f64, usize)], depth: f64) -> Option<usize> {
+    let i = samples.partition_point(|&(d, _)| d < depth - DEPTH_EPSILON);
+    samples
+        .get(i)
+        .filter(|&&(d, _)| (d - depth).abs() <= DEPTH_EPSILON)
+        .map(|&(_, row)| row)
+}
+
+// Original text of a value, None when it is NULL. Text values (eg. LAS 3.0 strings) are kept.
+fn cell(las_file: &LasFile, column: usize, row: usize) -> Option<String> {
+    let curve = las_file.ascii_log_data.curves.get(column)?;
+    let raw = curve.raw().get(row)?;
+    let is_null = curve.null_value().is_some_and(|null| curve.values()[row] == null);
+    (!is_null).then(|| raw.clone())
+}
+
+// (run, first depth, last depth) of each stretch of rows supplied by the same run.
+fn intervals<'a>(rows: &[(f64, usize, usize)], data_rows: &'a [Vec<String>]) -> Vec<(usize, &'a str, &'a str)> {
+    let mut intervals: Vec<(usize, &str, &str)> = vec![];
+    for (&(_, run, _), data_row) in rows.iter().zip(data_rows) {
+        let depth = data_row[0].as_str();
+        match intervals.last_mut() {
+            Some(last) if last.0 == run => last.2 = depth,
+            _ => intervals.push((run, depth, depth)),
+        }
+    }
+    intervals
+}
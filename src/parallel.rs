@@ -0,0 +1,203 @@
+use crate::{Delimiter, InvalidLineKind, LasHeader, ParseError, parse, parse_header};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::fs::File;
+
+// Chunks of "~A" are at least this big, so small files aren't split up for nothing.
+const MIN_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// A .las file read by [`parse_parallel`] : every header section, and ASCII log data as one column of numbers
+/// per curve. Nothing is kept as text, which is what makes it fast, so it can't be written back out as is.
+#[derive(Debug, Default)]
+pub struct ColumnarLasFile {
+    pub header: LasHeader,
+    /// One column per curve, in the order of the Curve section. NULL values are left as is (see
+    /// [`ColumnarLasFile::null_value`]) and cells that are not numeric are `NaN`, same as [`crate::sections::Curve`].
+    pub columns: Vec<Vec<f64>>,
+}
+
+impl ColumnarLasFile {
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    /// Column by curve mnemonic (case insensitive).
+    pub fn column(&self, mnemonic: &str) -> Option<&[f64]> {
+        let curves = &self.header.curve_information.curves;
+        let i = curves.iter().position(|c| c.mnemonic.eq_ignore_ascii_case(mnemonic))?;
+        self.columns.get(i).map(Vec::as_slice)
+    }
+
+    /// "NULL" from the Well section.
+    pub fn null_value(&self) -> Option<f64> {
+        self.header.well_information.null_value()
+    }
+}
+
+/// Parses a (multi GB) .las file on every core. The file is memory mapped and "~A" is split into chunks at line
+/// boundaries, which are parsed at the same time straight into numbers, then put back together in order.
+///
+/// Errors are the same as [`crate::parse`], with line numbers counted from the top of the file, so ASCII log data
+/// can't contain comments or blank lines either. Wrapped
+/// ("WRAP. YES") files can't be split at any line, so they are parsed with [`crate::parse`] instead.
+pub fn parse_parallel(las_file_path: &str) -> Result<ColumnarLasFile, ParseError> {
+    let chunk_size = MIN_CHUNK_SIZE.max(file_len(las_file_path)? / (rayon::current_num_threads() * 4));
+    parse_chunked(las_file_path, chunk_size)
+}
+
+pub(crate) fn parse_chunked(las_file_path: &str, chunk_size: usize) -> Result<ColumnarLasFile, ParseError> {
+    let header = parse_header(las_file_path)?;
+    let num_curves = header.curve_information.curves.len();
+    if header.version_information.is_wrapped() {
+        let las_file = parse(las_file_path)?;
+        let columns = las_file
+            .ascii_log_data
+            .curves
+            .iter()
+            .map(|c| c.values().to_vec())
+            .collect();
+        return Ok(ColumnarLasFile { header, columns });
+    }
+    let Some(data_offset) = header.data_offset else {
+        return Ok(ColumnarLasFile {
+            header,
+            columns: vec![vec![]; num_curves],
+        });
+    };
+
+    let file = File::open(las_file_path)?;
+    // SAFETY: the map is only read from, and dropped before we return. As with any memory map, another
+    // process truncating the file while we read it is undefined behaviour.
+    let mmap = unsafe { Mmap::map(&file)? };
+    let (head, data) = mmap.split_at((data_offset as usize).min(mmap.len()));
+    let delimiter = header.version_information.delimiter();
+
+    let chunks = split_lines(data, chunk_size);
+    let parsed: Vec<Result<Chunk, ParseError>> = chunks
+        .par_iter()
+        .map(|chunk| parse_chunk(chunk, num_curves, delimiter))
+        .collect();
+
+    // Lines before the current chunk.
+    let mut num_lines = head.iter().filter(|&&b| b == b'\n').count();
+    let num_rows = parsed.iter().flatten().map(|chunk| chunk.num_rows).sum();
+    let mut columns: Vec<Vec<f64>> = (0..num_curves).map(|_| Vec::with_capacity(num_rows)).collect();
+    for result in parsed {
+        let chunk = result.map_err(|e| with_line_offset(e, num_lines))?;
+        for (column, values) in columns.iter_mut().zip(chunk.columns) {
+            column.extend(values);
+        }
+        num_lines += chunk.num_lines;
+        if chunk.reached_section {
+            break;
+        }
+    }
+    Ok(ColumnarLasFile { header, columns })
+}
+
+struct Chunk {
+    columns: Vec<Vec<f64>>,
+    num_rows: usize,
+    num_lines: usize,
+    // Found a LAS 3.0 section after log data, anything after it isn't log data.
+    reached_section: bool,
+}
+
+// Splits `data` into chunks of about `chunk_size` bytes, each ending after a '\n' (or at the end of `data`).
+fn split_lines(data: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    let mut chunks = vec![];
+    let mut rest = data;
+    while rest.len() > chunk_size {
+        let end = rest[chunk_size..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |i| chunk_size + i + 1);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+    chunks
+}
+
+// Same as `LasParser::parse_ascii_data_line` in strict mode, without a String per cell. Line numbers in errors
+// are counted from the start of the chunk.
+fn parse_chunk(bytes: &[u8], num_curves: usize, delimiter: Delimiter) -> Result<Chunk, ParseError> {
+    let mut chunk = Chunk {
+        columns: vec![vec![]; num_curves],
+        num_rows: 0,
+        num_lines: 0,
+        reached_section: false,
+    };
+    for line in bytes.split_inclusive(|&b| b == b'\n') {
+        chunk.num_lines += 1;
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim();
+        let line_kind = match line {
+            "" => Some(InvalidLineKind::Empty),
+            _ if line.starts_with('#') => Some(InvalidLineKind::Comment),
+            _ => None,
+        };
+        if let Some(line_kind) = line_kind {
+            return Err(ParseError::AsciiDataContainsInvalidLine {
+                line_number: chunk.num_lines,
+                line_kind,
+            });
+        }
+        if line.starts_with('~') {
+            chunk.reached_section = true;
+            break;
+        }
+
+        let mut num_values = 0;
+        let mut push = |value: &str| {
+            if let Some(column) = chunk.columns.get_mut(num_values) {
+                column.push(value.parse().unwrap_or(f64::NAN));
+            }
+            num_values += 1;
+        };
+        match delimiter {
+            // Quoted values can contain the delimiter.
+            _ if line.contains('"') => delimiter.split(line).iter().for_each(|v| push(v)),
+            Delimiter::Space => line.split_ascii_whitespace().for_each(push),
+            Delimiter::Comma => line.split(',').for_each(|v| push(v.trim())),
+            Delimiter::Tab => line.split('\t').for_each(|v| push(v.trim())),
+        }
+        if num_values != num_curves {
+            return Err(ParseError::AsciiColumnsMismatch {
+                line_number: chunk.num_lines,
+                num_cols_in_headers: num_curves,
+                num_cols_in_row: num_values,
+            });
+        }
+        chunk.num_rows += 1;
+    }
+    Ok(chunk)
+}
+
+fn with_line_offset(e: ParseError, offset: usize) -> ParseError {
+    match e {
+        ParseError::AsciiColumnsMismatch {
+            line_number,
+            num_cols_in_headers,
+            num_cols_in_row,
+        } => ParseError::AsciiColumnsMismatch {
+            line_number: line_number + offset,
+            num_cols_in_headers,
+            num_cols_in_row,
+        },
+        ParseError::AsciiDataContainsInvalidLine { line_number, line_kind } => {
+            ParseError::AsciiDataContainsInvalidLine {
+                line_number: line_number + offset,
+                line_kind,
+            }
+        }
+        e => e,
+    }
+}
+
+fn file_len(las_file_path: &str) -> Result<usize, ParseError> {
+    Ok(std::fs::metadata(las_file_path)?.len() as usize)
+}
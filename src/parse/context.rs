@@ -1,6 +1,9 @@
 use std::collections::HashMap;
 
-use crate::parse::{SectionKind, state::ParserState};
+use crate::{
+    Diagnostic, Section,
+    parse::{Delimiter, LasValue, ParseOptions, SectionKind, depth_check::DepthCheck, state::ParserState},
+};
 
 #[derive(Debug, Default)]
 pub(crate) struct ParserContext {
@@ -8,6 +11,43 @@ pub(crate) struct ParserContext {
     pub curve_mnemonics: Vec<String>,
     pub comments: PendingComments,
     pub state: ParserState,
+    // Set from the "WRAP" line in the Version section.
+    pub wrap: bool,
+    // Holds the values of a wrapped depth step until we have collected one value per curve.
+    pub wrapped_row: Vec<String>,
+    // Line number that the current wrapped depth step started on.
+    pub wrapped_row_line_number: usize,
+    // Set when "VERS" in the Version section is 3.x
+    pub las3: bool,
+    // Set from the "DLM" line in the Version section (LAS 3.0 only).
+    pub delimiter: Delimiter,
+    // LAS 3.0 section groups we have seen, keyed by lowercased section title, eg. "core_data[1]".
+    pub group_sections: HashMap<String, usize>,
+    // LAS 3.0 section group mnemonics, keyed by lowercased group name, eg. "core".
+    pub group_mnemonics: HashMap<String, Vec<String>>,
+    // Lowercased name of the section group we are currently in.
+    pub current_group: Option<String>,
+    pub options: ParseOptions,
+    // Problems we recovered from in lenient mode.
+    pub diagnostics: Vec<Diagnostic>,
+    // Set in lenient mode after a section we could not use, everything up to the next header is ignored.
+    pub skipping: bool,
+    // Raw "NULL" value from the Well section, used to pad short rows in lenient mode.
+    pub null_text: Option<String>,
+    // Line number and text of the first curve, for diagnostics.
+    pub first_curve_line: Option<(usize, String)>,
+    // Line number and text of each data line in the Well section, keyed by uppercased mnemonic, for diagnostics.
+    pub well_lines: HashMap<String, (usize, String)>,
+    // Copy of the Version or Well section being read, validated once the section ends.
+    pub header_section: Option<Section>,
+    // "STRT", "STOP" and "STEP" from the Well section, for `ParseOptions::validate_data`.
+    pub start: Option<LasValue>,
+    pub stop: Option<LasValue>,
+    pub step: Option<LasValue>,
+    // Created at the first row of ASCII log data when validating data.
+    pub depth_check: Option<DepthCheck>,
+    // Line number and text of the last row of ASCII log data, for diagnostics.
+    pub last_row_line: Option<(usize, String)>,
 }
 
 #[derive(Debug, Default)]
@@ -0,0 +1,179 @@
+use crate::{
+    ParseError,
+    parse::{Section, SectionEntry, SectionKind, Sink},
+};
+use std::io::Write;
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Column separator, ',' for CSV or '\t' for TSV.
+    pub separator: char,
+    /// Write values equal to the Well section "NULL" as empty cells.
+    pub null_as_empty: bool,
+    /// Write the Version and Well sections above the header row, as lines starting with '#'.
+    pub preamble: bool,
+}
+
+impl CsvOptions {
+    pub fn tsv() -> Self {
+        Self {
+            separator: '\t',
+            ..Default::default()
+        }
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            separator: ',',
+            null_as_empty: false,
+            preamble: false,
+        }
+    }
+}
+
+/// Streams ASCII log data as CSV (or TSV), one row per depth step.
+/// The header row is made up of curve mnemonics and their units, eg. "GR[API]".
+/// Everything before "~A" is small, so we only hold onto what we need from it.
+pub struct CsvSink<W>
+where
+    W: Write,
+{
+    writer: W,
+    options: CsvOptions,
+    current_kind: Option<SectionKind>,
+    // "MNEM[UNIT]" for each curve in the Curve section.
+    curve_labels: Vec<String>,
+    null: Option<f64>,
+    preamble: Vec<String>,
+}
+
+impl<W> CsvSink<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W, options: CsvOptions) -> Self {
+        Self {
+            writer,
+            options,
+            current_kind: None,
+            curve_labels: vec![],
+            null: None,
+            preamble: vec![],
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_row<'a>(&mut self, cells: impl Iterator<Item = &'a str>) -> Result<(), ParseError> {
+        let mut line = String::new();
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                line.push(self.options.separator);
+            }
+            line.push_str(&self.quote(cell));
+        }
+        writeln!(self.writer, "{line}")?;
+        Ok(())
+    }
+
+    // Quote cells that contain the separator, a quote, or a line break, doubling any quotes.
+    fn quote(&self, cell: &str) -> String {
+        if cell.contains([self.options.separator, '"', '\n', '\r']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.to_string()
+        }
+    }
+
+    fn is_null(&self, cell: &str) -> bool {
+        match (self.null, cell.parse::<f64>()) {
+            (Some(null), Ok(value)) => value == null,
+            _ => false,
+        }
+    }
+}
+
+impl<W> Sink for CsvSink<W>
+where
+    W: Write,
+{
+    fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
+        let kind = section.header.kind;
+        self.current_kind = Some(kind);
+
+        if self.options.preamble && matches!(kind, SectionKind::Version | SectionKind::Well) {
+            self.preamble.push(format!("~{}", section.header.raw));
+        }
+
+        if kind == SectionKind::AsciiLogData {
+            for line in std::mem::take(&mut self.preamble) {
+                writeln!(self.writer, "# {}", line.trim_end())?;
+            }
+
+            let headers = section.ascii_headers.unwrap_or_default();
+            let labels: Vec<String> = headers
+                .iter()
+                .enumerate()
+                .map(|(i, mnemonic)| self.curve_labels.get(i).cloned().unwrap_or_else(|| mnemonic.clone()))
+                .collect();
+            self.write_row(labels.iter().map(String::as_str))?;
+        }
+
+        Ok(())
+    }
+
+    fn entry(&mut self, entry: SectionEntry) -> Result<(), ParseError> {
+        let SectionEntry::Delimited(mut data_line) = entry else {
+            return Ok(());
+        };
+        // Comments would break up the preamble.
+        data_line.comments = None;
+
+        match self.current_kind {
+            Some(SectionKind::Curve) => {
+                let label = match &data_line.unit {
+                    Some(unit) => format!("{}[{unit}]", data_line.mnemonic),
+                    None => data_line.mnemonic.clone(),
+                };
+                self.curve_labels.push(label);
+            }
+            Some(SectionKind::Well) => {
+                if data_line.mnemonic.eq_ignore_ascii_case("NULL") {
+                    self.null = data_line.value.as_ref().and_then(|v| v.to_string().parse().ok());
+                }
+                if self.options.preamble {
+                    self.preamble.push(data_line.to_string());
+                }
+            }
+            Some(SectionKind::Version) if self.options.preamble => self.preamble.push(data_line.to_string()),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+        if !self.options.null_as_empty {
+            return self.write_row(row.iter().map(String::as_str));
+        }
+        let cells: Vec<&str> = row
+            .iter()
+            .map(|cell| if self.is_null(cell) { "" } else { cell.as_str() })
+            .collect();
+        self.write_row(cells.into_iter())
+    }
+
+    fn section_end(&mut self) -> Result<(), ParseError> {
+        self.current_kind = None;
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
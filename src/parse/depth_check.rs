@@ -0,0 +1,117 @@
+use crate::{LasValue, ParseError, decimal_places, resample::DEPTH_EPSILON};
+
+/// Checks the index of ASCII log data, one depth at a time, against "STRT", "STOP" and "STEP".
+/// Shared by the parser (see [`crate::ParseOptions::validate_data`]) and [`crate::sections::WellInformation::validate_data`].
+#[derive(Debug, Default)]
+pub(crate) struct DepthCheck {
+    start: Option<(f64, f64)>,
+    stop: Option<(f64, f64)>,
+    step: Option<f64>,
+    // (depth, how far it may be off from rounding) of the previous row.
+    previous: Option<(f64, f64)>,
+    // Set from "STRT" and "STOP", otherwise from the first two depths.
+    ascending: Option<bool>,
+    // Distance between the first two depths, and whether every pair since has been the same distance apart.
+    spacing: Option<f64>,
+    regular: Option<bool>,
+}
+
+impl DepthCheck {
+    pub fn new(start: Option<&LasValue>, stop: Option<&LasValue>, step: Option<&LasValue>) -> Self {
+        let with_rounding = |value: Option<&LasValue>| {
+            let value = value?;
+            Some((value.as_f64()?, rounding(&value.to_string())))
+        };
+        let (start, stop) = (with_rounding(start), with_rounding(stop));
+        Self {
+            start,
+            stop,
+            step: step.and_then(LasValue::as_f64),
+            ascending: match (start, stop) {
+                (Some((start, _)), Some((stop, _))) if start != stop => Some(start < stop),
+                _ => None,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Checks the depth of the next row, eg. "1670.000". Depths that aren't numeric are skipped.
+    pub fn row(&mut self, raw_depth: &str) -> Vec<ParseError> {
+        let Ok(depth) = raw_depth.parse::<f64>() else {
+            return vec![];
+        };
+        let depth_rounding = rounding(raw_depth);
+        let mut errors = vec![];
+
+        let Some((previous, previous_rounding)) = self.previous.replace((depth, depth_rounding)) else {
+            if let Some((start, start_rounding)) = self.start
+                && (depth - start).abs() > depth_rounding.max(start_rounding)
+            {
+                errors.push(ParseError::DepthMismatch {
+                    mnemonic: "STRT".to_string(),
+                    expected: start,
+                    found: depth,
+                });
+            }
+            return errors;
+        };
+
+        let difference = depth - previous;
+        let ascending = *self.ascending.get_or_insert(difference > 0.0);
+        if difference == 0.0 || (difference > 0.0) != ascending {
+            errors.push(ParseError::NonMonotonicIndex { previous, depth });
+            self.regular = Some(false);
+            return errors;
+        }
+
+        // Both depths were rounded when they were written.
+        let tolerance = 2.0 * depth_rounding.max(previous_rounding);
+        match self.spacing {
+            None => self.spacing = Some(difference),
+            Some(spacing) => {
+                let same = (difference - spacing).abs() <= tolerance;
+                self.regular = Some(self.regular.unwrap_or(true) && same);
+            }
+        }
+        if let Some(step) = self.step.filter(|&s| s != 0.0)
+            && (difference - step).abs() > tolerance
+        {
+            errors.push(ParseError::InconsistentDepthStep {
+                step,
+                found: difference,
+                depth,
+            });
+        }
+        errors
+    }
+
+    /// Checks the last depth against "STOP", and a "STEP" of 0 against the spacing of the depths.
+    /// Returns the "STOP" problem (which belongs to the last row) separately.
+    pub fn finish(self) -> (Option<ParseError>, Option<ParseError>) {
+        let stop = match (self.previous, self.stop) {
+            (Some((depth, depth_rounding)), Some((stop, stop_rounding)))
+                if (depth - stop).abs() > depth_rounding.max(stop_rounding) =>
+            {
+                Some(ParseError::DepthMismatch {
+                    mnemonic: "STOP".to_string(),
+                    expected: stop,
+                    found: depth,
+                })
+            }
+            _ => None,
+        };
+        let undeclared_step = match (self.step, self.spacing) {
+            (Some(step), Some(spacing)) if step == 0.0 && self.regular == Some(true) => {
+                Some(ParseError::UndeclaredStep { step: spacing })
+            }
+            _ => None,
+        };
+        (stop, undeclared_step)
+    }
+}
+
+// How far a value may be from what it was before it was rounded to the decimals it was written with,
+// with room for float error on top.
+fn rounding(raw: &str) -> f64 {
+    0.5 * 10f64.powi(-(decimal_places(raw) as i32)) + DEPTH_EPSILON
+}
@@ -0,0 +1,232 @@
+use crate::{
+    LasValue, ParseError, decimal_places,
+    parse::{DataLine, Section, SectionEntry, SectionKind, Sink},
+    resample::DEPTH_EPSILON,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct FilterOptions {
+    /// Keep rows of ASCII log data at or below this depth.
+    pub top: Option<f64>,
+    /// Keep rows of ASCII log data at or above this depth.
+    pub base: Option<f64>,
+    /// Keep only these curves (case insensitive), in the order they are in the file. The index curve is always kept.
+    pub curves: Option<Vec<String>>,
+}
+
+impl FilterOptions {
+    fn filters_depth(&self) -> bool {
+        self.top.is_some() || self.base.is_some()
+    }
+
+    // Shallowest and deepest depth to keep, whichever way round `top` and `base` were given.
+    fn depth_range(&self) -> (f64, f64) {
+        let top = self.top.unwrap_or(f64::NEG_INFINITY);
+        let base = self.base.unwrap_or(f64::INFINITY);
+        (top.min(base), top.max(base))
+    }
+
+    pub(crate) fn keeps_depth(&self, depth: f64) -> bool {
+        let (top, base) = self.depth_range();
+        depth >= top - DEPTH_EPSILON && depth <= base + DEPTH_EPSILON
+    }
+
+    pub(crate) fn keeps_curve(&self, mnemonic: &str) -> bool {
+        self.curves
+            .as_ref()
+            .is_none_or(|curves| curves.iter().any(|c| c.eq_ignore_ascii_case(mnemonic)))
+    }
+}
+
+/// Crops ASCII log data to a depth range and drops curves on the way into another [`Sink`],
+/// so only the part of the file you want is ever written, eg. straight into a [`crate::CsvSink`].
+/// The Curve section and "~A" headers match the curves kept. "STRT" and "STOP" are worked out
+/// from "STEP", as the rows haven't been read yet when the Well section is handed over.
+pub struct FilterSink<S>
+where
+    S: Sink,
+{
+    inner: S,
+    options: FilterOptions,
+    current_kind: Option<SectionKind>,
+    // The Well section is held onto until it ends, "STEP" usually comes after "STRT" and "STOP".
+    well_entries: Vec<SectionEntry>,
+    // Position of each curve we keep, in the Curve section and in each row.
+    columns: Vec<usize>,
+    // Every curve in the Curve section, kept or not.
+    mnemonics: Vec<String>,
+}
+
+impl<S> FilterSink<S>
+where
+    S: Sink,
+{
+    pub fn new(inner: S, options: FilterOptions) -> Self {
+        Self {
+            inner,
+            options,
+            current_kind: None,
+            well_entries: vec![],
+            columns: vec![],
+            mnemonics: vec![],
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn flush_well_entries(&mut self) -> Result<(), ParseError> {
+        let mut entries = std::mem::take(&mut self.well_entries);
+        let find = |mnemonic: &str| {
+            entries.iter().find_map(|e| match e {
+                SectionEntry::Delimited(line) if line.mnemonic.eq_ignore_ascii_case(mnemonic) => line.value.clone(),
+                _ => None,
+            })
+        };
+        let (start, stop, step) = (find("STRT"), find("STOP"), find("STEP"));
+        if let (Some(start), Some(stop)) = (start, stop) {
+            let cropped = crop_range(&start, &stop, step.as_ref(), self.options.depth_range());
+            if let Some((new_start, new_stop)) = cropped {
+                for entry in entries.iter_mut() {
+                    let SectionEntry::Delimited(line) = entry else {
+                        continue;
+                    };
+                    set_if(line, "STRT", &new_start);
+                    set_if(line, "STOP", &new_stop);
+                }
+            }
+        }
+        for entry in entries {
+            self.inner.entry(entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S> Sink for FilterSink<S>
+where
+    S: Sink,
+{
+    fn start(&mut self) -> Result<(), ParseError> {
+        self.inner.start()
+    }
+
+    fn section_start(&mut self, mut section: Section) -> Result<(), ParseError> {
+        let kind = section.header.kind;
+        self.current_kind = Some(kind);
+        if kind == SectionKind::Curve {
+            self.columns.clear();
+            self.mnemonics.clear();
+        }
+        if kind == SectionKind::AsciiLogData
+            && self.options.curves.is_some()
+            && let Some(headers) = section.ascii_headers.as_mut()
+        {
+            *headers = self.columns.iter().filter_map(|&i| headers.get(i).cloned()).collect();
+        }
+        self.inner.section_start(section)
+    }
+
+    fn entry(&mut self, entry: SectionEntry) -> Result<(), ParseError> {
+        match (self.current_kind, &entry) {
+            (Some(SectionKind::Well), _) if self.options.filters_depth() => {
+                self.well_entries.push(entry);
+                Ok(())
+            }
+            (Some(SectionKind::Curve), SectionEntry::Delimited(line)) => {
+                let position = self.mnemonics.len();
+                self.mnemonics.push(line.mnemonic.clone());
+                if position != 0 && !self.options.keeps_curve(&line.mnemonic) {
+                    return Ok(());
+                }
+                self.columns.push(position);
+                self.inner.entry(entry)
+            }
+            _ => self.inner.entry(entry),
+        }
+    }
+
+    fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+        if self.options.filters_depth() {
+            let depth = row.first().and_then(|d| d.parse::<f64>().ok());
+            if !depth.is_some_and(|d| self.options.keeps_depth(d)) {
+                return Ok(());
+            }
+        }
+        if self.options.curves.is_none() {
+            return self.inner.ascii_row(row);
+        }
+        let row: Vec<String> = self.columns.iter().filter_map(|&i| row.get(i).cloned()).collect();
+        self.inner.ascii_row(&row)
+    }
+
+    fn section_end(&mut self) -> Result<(), ParseError> {
+        match self.current_kind {
+            Some(SectionKind::Well) => self.flush_well_entries()?,
+            Some(SectionKind::Curve) => {
+                let missing = self
+                    .options
+                    .curves
+                    .iter()
+                    .flatten()
+                    .find(|c| !self.mnemonics.iter().any(|m| m.eq_ignore_ascii_case(c)));
+                if let Some(missing) = missing {
+                    return Err(missing_curve(missing));
+                }
+            }
+            _ => {}
+        }
+        self.current_kind = None;
+        self.inner.section_end()
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.inner.end()
+    }
+}
+
+pub(crate) fn missing_curve(mnemonic: &str) -> ParseError {
+    ParseError::MissingCurve {
+        mnemonic: mnemonic.to_string(),
+    }
+}
+
+fn set_if(line: &mut DataLine, mnemonic: &str, value: &str) {
+    if line.mnemonic.eq_ignore_ascii_case(mnemonic) {
+        line.value = LasValue::new(value);
+    }
+}
+
+// New "STRT" and "STOP", on the first and last depth steps between `top` and `base`.
+// Without a "STEP" they are clamped to `top` and `base`. None when nothing is left, or "STEP" makes no sense.
+fn crop_range(
+    start: &LasValue,
+    stop: &LasValue,
+    step: Option<&LasValue>,
+    (top, base): (f64, f64),
+) -> Option<(String, String)> {
+    let (start_value, stop_value) = (start.as_f64()?, stop.as_f64()?);
+    let step_value = step.and_then(LasValue::as_f64).filter(|&s| s != 0.0);
+    let decimals = decimal_places(&start.to_string()).max(step.map_or(0, |s| decimal_places(&s.to_string())));
+
+    let ascending = start_value <= stop_value;
+    let (near, far) = if ascending { (top, base) } else { (base, top) };
+    let (new_start, new_stop) = match step_value {
+        Some(step) => {
+            let last = ((stop_value - start_value) / step + DEPTH_EPSILON).floor();
+            if last < 0.0 {
+                return None;
+            }
+            let first_step = ((near - start_value) / step - DEPTH_EPSILON).ceil().clamp(0.0, last);
+            let last_step = ((far - start_value) / step + DEPTH_EPSILON).floor().clamp(0.0, last);
+            if first_step > last_step {
+                return None;
+            }
+            (start_value + first_step * step, start_value + last_step * step)
+        }
+        None if ascending => (start_value.max(top), stop_value.min(base)),
+        None => (start_value.min(base), stop_value.max(top)),
+    };
+    Some((format!("{new_start:.decimals$}"), format!("{new_stop:.decimals$}")))
+}
@@ -1,21 +1,42 @@
 use crate::{
     ParseError,
     parse::{Section, SectionEntry, SectionKind, Sink},
-    sections::{CurveInformation, OtherInformation, ParameterInformation, VersionInformation, WellInformation},
+    sections::{
+        CurveInformation, OtherInformation, ParameterInformation, SectionGroup, VersionInformation, WellInformation,
+    },
 };
 use serde::Serialize;
 use std::io::Write;
 
+#[derive(Debug, Default, Clone)]
+pub struct JsonOptions {
+    /// Write ASCII log data as one array per curve, `"columns":{"GR":[...]}`, instead of one array per row.
+    /// Every value has to be held in memory until the end of "~A", as rows come in one at a time.
+    pub columnar: bool,
+}
+
+/// Streams a LAS file as JSON, without building a [`crate::LasFile`].
+///
+/// Numeric ASCII log data is written as JSON numbers and values equal to the Well section "NULL" as `null`.
+/// Anything else (eg. LAS 3.0 string data) is written as a string.
 // We store every section outside of AsciiLogData within the 'current_section'.
 // Those sections are very small in comparison to ascii data. We directly stream
-// and write the ascii data to the writer, no allocations or buffering.
-pub(crate) struct JsonSink<W>
+// and write the ascii data to the writer, no allocations or buffering (unless columnar).
+pub struct JsonSink<W>
 where
     W: Write,
 {
     writer: W,
+    options: JsonOptions,
     current_section: Option<Section>,
+    // Whether a key has been written to the top level object yet, so we know when a comma is needed.
+    wrote_key: bool,
     is_first_ascii_row: bool,
+    null: Option<f64>,
+    // Columnar mode only : every value of each curve, already written as JSON.
+    columns: Vec<Vec<String>>,
+    // LAS 3.0 section groups can come before or after log data, so we hold onto them until the end.
+    section_groups: Vec<SectionGroup>,
 }
 
 impl<W> JsonSink<W>
@@ -23,21 +44,70 @@ where
     W: Write,
 {
     pub fn new(writer: W) -> Self {
+        Self::with_options(writer, JsonOptions::default())
+    }
+
+    pub fn with_options(writer: W, options: JsonOptions) -> Self {
         Self {
             writer,
+            options,
             current_section: None,
+            wrote_key: false,
             is_first_ascii_row: true,
+            null: None,
+            columns: vec![],
+            section_groups: vec![],
         }
     }
 
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    // Writes `"key":`, after a comma if it isn't the first key of the top level object.
+    fn write_key(&mut self, key: &str) -> Result<(), ParseError> {
+        if self.wrote_key {
+            write!(self.writer, ",")?;
+        }
+        self.wrote_key = true;
+        self.write_json(&key)?;
+        write!(self.writer, ":")?;
+        Ok(())
+    }
+
+    fn write_json<T>(&mut self, value: &T) -> Result<(), ParseError>
+    where
+        T: Serialize + ?Sized,
+    {
+        serde_json::to_writer(&mut self.writer, value).map_err(|e| ParseError::Error { message: e.to_string() })
+    }
+
     fn write_section<T>(&mut self, section_name: &str, section: &T) -> Result<(), ParseError>
     where
         T: Serialize,
     {
-        write!(self.writer, "\"{section_name}\":")?;
-        serde_json::to_writer(&mut self.writer, section).map_err(|e| ParseError::Error { message: e.to_string() })?;
+        self.write_key(section_name)?;
+        self.write_json(section)
+    }
+
+    fn write_columns(&mut self, headers: &[String]) -> Result<(), ParseError> {
+        write!(self.writer, "\"columns\":{{")?;
+        let columns = std::mem::take(&mut self.columns);
+        for (i, values) in columns.iter().enumerate() {
+            if i != 0 {
+                write!(self.writer, ",")?;
+            }
+            let header = headers.get(i).cloned().unwrap_or_else(|| i.to_string());
+            self.write_json(&header)?;
+            write!(self.writer, ":[{}]", values.join(","))?;
+        }
+        write!(self.writer, "}}")?;
         Ok(())
     }
+
+    fn cell_json(&self, cell: &str) -> String {
+        cell_json(cell, self.null)
+    }
 }
 
 impl<W> Sink for JsonSink<W>
@@ -50,16 +120,33 @@ where
     }
 
     fn end(&mut self) -> Result<(), ParseError> {
-        write!(self.writer, "}}}}")?;
+        if !self.section_groups.is_empty() {
+            let section_groups = std::mem::take(&mut self.section_groups);
+            self.write_section("SectionGroups", &section_groups)?;
+        }
+        write!(self.writer, "}}")?;
+        self.writer.flush()?;
         Ok(())
     }
 
     fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
         if section.header.kind == SectionKind::AsciiLogData {
-            write!(self.writer, "\"AsciiLogData\":{{\"headers\":")?;
-            serde_json::to_writer(&mut self.writer, &section.ascii_headers)
-                .map_err(|e| ParseError::Error { message: e.to_string() })?;
-            write!(self.writer, ",\"rows\":[")?;
+            // Rows go last, so they can be streamed back into a .las file after its "~A" line.
+            self.write_key("AsciiLogData")?;
+            write!(self.writer, "{{\"headers\":")?;
+            self.write_json(&section.ascii_headers)?;
+            write!(self.writer, ",\"comments\":")?;
+            self.write_json(&section.comments)?;
+            write!(self.writer, ",\"header\":")?;
+            self.write_json(&format!("~{}", section.header.raw))?;
+            write!(self.writer, ",")?;
+            if self.options.columnar {
+                let num_columns = section.ascii_headers.as_ref().map_or(0, Vec::len);
+                self.columns = vec![vec![]; num_columns];
+            } else {
+                write!(self.writer, "\"rows\":[")?;
+            }
+            self.is_first_ascii_row = true;
         }
         self.current_section = Some(section);
         Ok(())
@@ -73,17 +160,29 @@ where
     }
 
     fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+        if self.options.columnar {
+            if self.columns.len() < row.len() {
+                self.columns.resize(row.len(), vec![]);
+            }
+            for (i, cell) in row.iter().enumerate() {
+                let json = self.cell_json(cell);
+                self.columns[i].push(json);
+            }
+            return Ok(());
+        }
+
         if !self.is_first_ascii_row {
             write!(self.writer, ",")?;
         }
         self.is_first_ascii_row = false;
 
         write!(self.writer, "[")?;
-        for (i, r) in row.iter().enumerate() {
+        for (i, cell) in row.iter().enumerate() {
             if i != 0 {
                 write!(self.writer, ",")?;
             }
-            write!(self.writer, "\"{}\"", r)?;
+            let json = self.cell_json(cell);
+            self.writer.write_all(json.as_bytes())?;
         }
         write!(self.writer, "]")?;
 
@@ -91,34 +190,78 @@ where
     }
 
     fn section_end(&mut self) -> Result<(), ParseError> {
-        if let Some(section) = self.current_section.take() {
-            let kind = section.header.kind;
-
-            match kind {
-                SectionKind::AsciiLogData => {
-                    write!(self.writer, "],\"comments\":")?;
-                    serde_json::to_writer(&mut self.writer, &section.comments)
-                        .map_err(|e| ParseError::Error { message: e.to_string() })?;
-                    write!(self.writer, ",\"header\":\"~{}\"", section.header.raw)?;
-                }
-                SectionKind::Well => self.write_section("WellInformation", &WellInformation::try_from(section)?)?,
-                SectionKind::Curve => self.write_section("CurveInformation", &CurveInformation::try_from(section)?)?,
-                SectionKind::Other => self.write_section("OtherInformation", &OtherInformation::try_from(section)?)?,
-                SectionKind::Version => {
-                    self.write_section("VersionInformation", &VersionInformation::try_from(section)?)?
-                }
-                SectionKind::Parameter => {
-                    self.write_section("ParameterInformation", &ParameterInformation::try_from(section)?)?
-                }
-            };
+        let Some(section) = self.current_section.take() else {
+            return Ok(());
+        };
 
-            // AsciiLogData is expected to be the last section in a .las file.
-            // That is how we can get away with making these assumptions.
-            if kind != SectionKind::AsciiLogData {
-                write!(self.writer, ",")?;
+        match section.header.kind {
+            SectionKind::AsciiLogData => {
+                if self.options.columnar {
+                    self.write_columns(section.ascii_headers.as_deref().unwrap_or_default())?;
+                } else {
+                    write!(self.writer, "]")?;
+                }
+                write!(self.writer, "}}")?;
             }
-        }
+            SectionKind::GroupParameter | SectionKind::GroupDefinition | SectionKind::GroupData => {
+                SectionGroup::add_section(&mut self.section_groups, section)?;
+            }
+            SectionKind::Well => {
+                let well = WellInformation::from_section(section)?;
+                self.null = well.null_value();
+                self.write_section("WellInformation", &well)?;
+            }
+            SectionKind::Curve => self.write_section("CurveInformation", &CurveInformation::try_from(section)?)?,
+            SectionKind::Other => self.write_section("OtherInformation", &OtherInformation::try_from(section)?)?,
+            SectionKind::Version => {
+                self.write_section("VersionInformation", &VersionInformation::from_section(section)?)?
+            }
+            SectionKind::Parameter => {
+                self.write_section("ParameterInformation", &ParameterInformation::try_from(section)?)?
+            }
+        };
 
         Ok(())
     }
 }
+
+// A value of ASCII log data as JSON : a number, `null` for the NULL sentinel, or an escaped string.
+pub(crate) fn cell_json(cell: &str, null: Option<f64>) -> String {
+    match cell.parse::<f64>() {
+        Ok(value) if null == Some(value) => "null".to_string(),
+        // The original text where JSON allows it, so "1670.000" isn't written as "1670.0".
+        Ok(value) if value.is_finite() && is_json_number(cell) => cell.to_string(),
+        Ok(value) if value.is_finite() => serde_json::Number::from_f64(value)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| "null".to_string()),
+        _ => serde_json::to_string(cell).unwrap_or_else(|_| "null".to_string()),
+    }
+}
+
+// Whether `raw` is already a valid JSON number, eg. "1670.000" is but "05.600", "1670." and "+1" aren't.
+fn is_json_number(raw: &str) -> bool {
+    let digits = |s: &str| s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    let rest = raw.strip_prefix('-').unwrap_or(raw);
+    let int_len = digits(rest);
+    if int_len == 0 || (int_len > 1 && rest.starts_with('0')) {
+        return false;
+    }
+    let mut rest = &rest[int_len..];
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let len = digits(fraction);
+        if len == 0 {
+            return false;
+        }
+        rest = &fraction[len..];
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        let len = digits(exponent);
+        if len == 0 {
+            return false;
+        }
+        rest = &exponent[len..];
+    }
+    rest.is_empty()
+}
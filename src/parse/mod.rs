@@ -1,14 +1,28 @@
 mod ast_sink;
 mod context;
+mod csv_sink;
+mod depth_check;
+mod filter_sink;
 mod json_sink;
+mod ndjson_sink;
+mod options;
+#[cfg(feature = "parquet")]
+mod parquet_sink;
 mod parser;
 mod state;
 mod yaml_sink;
 
 pub(crate) use ast_sink::*;
-pub(crate) use json_sink::*;
+pub use csv_sink::*;
+pub(crate) use depth_check::*;
+pub use filter_sink::*;
+pub use json_sink::*;
+pub use ndjson_sink::*;
+pub use options::*;
+#[cfg(feature = "parquet")]
+pub use parquet_sink::*;
 pub(crate) use parser::*;
-pub(crate) use yaml_sink::*;
+pub use yaml_sink::*;
 
 use crate::{ParseError, write_comments};
 use serde::{Deserialize, Serialize};
@@ -21,6 +35,27 @@ const REQUIRED_SECTIONS: [SectionKind; 4] = [
     SectionKind::AsciiLogData,
 ];
 
+// LAS 3.0 files are not required to contain log data.
+const REQUIRED_LAS3_SECTIONS: [SectionKind; 2] = [SectionKind::Version, SectionKind::Well];
+
+const ALLOWED_FIRST_CURVES: [&str; 4] = ["DEPT", "DEPTH", "TIME", "INDEX"];
+
+// There must be at least one curve, and the first one must be one of "DEPT", "DEPTH", "TIME" or "INDEX".
+pub(crate) fn validate_curve_mnemonics(mnemonics: &[String]) -> Result<(), ParseError> {
+    let expected_one_of = || ALLOWED_FIRST_CURVES.iter().map(|c| c.to_string()).collect();
+    match mnemonics.first() {
+        None => Err(ParseError::SectionMissingRequiredData {
+            section: SectionKind::Curve,
+            one_of: expected_one_of(),
+        }),
+        Some(first) if !ALLOWED_FIRST_CURVES.contains(&first.as_str()) => Err(ParseError::DisallowedFirstCurve {
+            got: first.clone(),
+            expected_one_of: expected_one_of(),
+        }),
+        Some(_) => Ok(()),
+    }
+}
+
 fn str_contains(str: &str, chars: &[char]) -> Vec<char> {
     let mut matches = vec![];
     for &c in chars {
@@ -43,19 +78,60 @@ pub enum SectionKind {
     Parameter,
     Other,
     AsciiLogData,
+    // LAS 3.0 section groups, eg. "~Core_Parameter", "~Core_Definition" and "~Core_Data".
+    // The "Log" group maps onto Parameter, Curve and AsciiLogData.
+    GroupParameter,
+    GroupDefinition,
+    GroupData,
 }
 
-impl From<&str> for SectionKind {
-    fn from(value: &str) -> Self {
-        match value {
-            v if v.starts_with("V") => SectionKind::Version,
-            v if v.starts_with("W") => SectionKind::Well,
-            v if v.starts_with("C") => SectionKind::Curve,
-            v if v.starts_with("P") => SectionKind::Parameter,
-            v if v.starts_with("O") => SectionKind::Other,
-            v if v.starts_with("A") => SectionKind::AsciiLogData,
-            _ => unreachable!("unrecognized section! {value}"),
+impl SectionKind {
+    // Works out what kind of section a header is, along with the name of the
+    // section group it belongs to for LAS 3.0 sections like "~Core_Data | Core_Definition".
+    // Before LAS 3.0 only the first letter counts, so "~Other_Data" is an Other section.
+    pub(crate) fn classify(raw: &str, las3: bool) -> Option<(SectionKind, Option<String>)> {
+        let title = section_title(raw);
+
+        if let Some((group, suffix)) = title.rsplit_once('_').filter(|_| las3) {
+            let is_log_group = group.eq_ignore_ascii_case("log");
+            let kind = match suffix.to_ascii_lowercase().as_str() {
+                "parameter" if is_log_group => Some(SectionKind::Parameter),
+                "definition" if is_log_group => Some(SectionKind::Curve),
+                "data" if is_log_group => Some(SectionKind::AsciiLogData),
+                "parameter" => Some(SectionKind::GroupParameter),
+                "definition" => Some(SectionKind::GroupDefinition),
+                "data" => Some(SectionKind::GroupData),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                let group = (!is_log_group).then(|| group.to_string());
+                return Some((kind, group));
+            }
         }
+
+        let kind = match title.chars().next()?.to_ascii_uppercase() {
+            'V' => SectionKind::Version,
+            'W' => SectionKind::Well,
+            'C' => SectionKind::Curve,
+            'P' => SectionKind::Parameter,
+            'O' => SectionKind::Other,
+            'A' => SectionKind::AsciiLogData,
+            _ => return None,
+        };
+        Some((kind, None))
+    }
+}
+
+// The first word of a section header, without any "[n]" index.
+// "Core_Data[1] | Core_Definition" -> "Core_Data"
+fn section_title(raw: &str) -> &str {
+    let title = raw
+        .split(|c: char| c.is_whitespace() || c == '|')
+        .next()
+        .unwrap_or_default();
+    match title.find('[') {
+        Some(i) => &title[..i],
+        None => title,
     }
 }
 
@@ -67,6 +143,7 @@ impl From<&str> for SectionKind {
 #[serde(untagged)]
 pub enum LasValue {
     Int(i64),
+    Float(LasFloat),
     Text(String),
 }
 
@@ -74,12 +151,22 @@ impl LasValue {
     pub fn new(value: &str) -> Option<Self> {
         Self::parse(value)
     }
+
+    /// The value as a number, if it is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            LasValue::Int(i) => Some(*i as f64),
+            LasValue::Float(f) => Some(f.value()),
+            LasValue::Text(_) => None,
+        }
+    }
 }
 
 impl fmt::Display for LasValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LasValue::Int(i) => write!(f, "{i}"),
+            LasValue::Float(float) => write!(f, "{float}"),
             LasValue::Text(t) => write!(f, "{t}"),
         }
     }
@@ -92,24 +179,148 @@ impl LasValue {
             None
         } else if let Ok(i) = raw.parse::<i64>() {
             Some(LasValue::Int(i))
+        } else if let Some(f) = LasFloat::parse(raw) {
+            Some(LasValue::Float(f))
         } else {
             Some(LasValue::Text(raw.to_string()))
         }
     }
 }
 
+// ================================================================================================
+// ------------------------ LasFloat --------------------------------------------------------------
+// ================================================================================================
+
+/// A decimal number along with the text it came from, so "1670.0000" is written back out as
+/// "1670.0000" and not "1670". Two floats are equal when their text is.
+#[derive(Debug, Clone)]
+pub struct LasFloat {
+    value: f64,
+    raw: String,
+}
+
+impl LasFloat {
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            raw: value.to_string(),
+        }
+    }
+
+    /// Only finite numbers, so "NaN" or "INF" stay text.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let value = raw.parse::<f64>().ok().filter(|v| v.is_finite())?;
+        Some(Self {
+            value,
+            raw: raw.to_string(),
+        })
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The text as it was written, eg. "-0.1250".
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl PartialEq for LasFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw
+    }
+}
+
+impl Eq for LasFloat {}
+
+impl fmt::Display for LasFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+// Written as its text, the same way it looks in the .las file.
+impl Serialize for LasFloat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.raw)
+    }
+}
+
+impl<'de> Deserialize<'de> for LasFloat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct LasFloatVisitor;
+
+        impl serde::de::Visitor<'_> for LasFloatVisitor {
+            type Value = LasFloat;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a finite number, or a string containing one")
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Some(LasFloat::new(v))
+                    .filter(|f| f.value.is_finite())
+                    .ok_or_else(|| E::custom("non-finite float"))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                LasFloat::parse(v).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        deserializer.deserialize_any(LasFloatVisitor)
+    }
+}
+
 // ================================================================================================
 // ------------------------ DataLine --------------------------------------------------------------
 // ================================================================================================
 
 // The sections "VERSION", "WELL", "CURVE" and "PARAMETER" use line delimiters.
-#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct DataLine {
     pub mnemonic: String,
     pub unit: Option<String>,
     pub value: Option<LasValue>,
     pub description: Option<String>,
     pub comments: Option<Vec<String>>,
+    // LAS 3.0 only : "{F}" format specifier, stored without braces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    // LAS 3.0 only : "| Run[1]" association, stored without the pipe.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub association: Option<String>,
+}
+
+impl DataLine {
+    /// Mnemonic without a LAS 3.0 array index, eg. "NMR[1]" -> "NMR".
+    pub fn base_mnemonic(&self) -> &str {
+        match self.mnemonic.find('[') {
+            Some(i) => &self.mnemonic[..i],
+            None => &self.mnemonic,
+        }
+    }
+
+    /// LAS 3.0 array index of the mnemonic, eg. "NMR[1]" -> Some(1).
+    pub fn array_index(&self) -> Option<usize> {
+        let open = self.mnemonic.find('[')?;
+        let index = self.mnemonic[open + 1..].strip_suffix(']')?;
+        index.trim().parse().ok()
+    }
 }
 
 impl fmt::Display for DataLine {
@@ -127,28 +338,146 @@ impl fmt::Display for DataLine {
         if let Some(description) = self.description.as_ref() {
             write!(f, " {description}")?;
         }
+        if let Some(format) = self.format.as_ref() {
+            write!(f, " {{{format}}}")?;
+        }
+        if let Some(association) = self.association.as_ref() {
+            write!(f, " | {association}")?;
+        }
         Ok(())
     }
 }
 
+// ================================================================================================
+// ------------------------ Delimiter -------------------------------------------------------------
+// ================================================================================================
+
+/// Separates values in ASCII log data. LAS 2.0 is always whitespace, LAS 3.0
+/// sets this with the "DLM" line in the Version section.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    #[default]
+    Space,
+    Comma,
+    Tab,
+}
+
+impl Delimiter {
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_uppercase().as_str() {
+            "SPACE" => Some(Delimiter::Space),
+            "COMMA" => Some(Delimiter::Comma),
+            "TAB" => Some(Delimiter::Tab),
+            _ => None,
+        }
+    }
+
+    // Splits a line of ASCII log data into values. Values wrapped in double quotes
+    // may contain the delimiter, eg. a formation name containing spaces.
+    pub(crate) fn split(&self, raw: &str) -> Vec<String> {
+        let separator = match self {
+            Delimiter::Space => return split_whitespace_quoted(raw),
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+        };
+        let mut values = vec![];
+        let mut value = String::new();
+        let mut in_quotes = false;
+        for c in raw.chars() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                c if c == separator && !in_quotes => values.push(std::mem::take(&mut value).trim().to_string()),
+                c => value.push(c),
+            }
+        }
+        values.push(value.trim().to_string());
+        values
+    }
+
+    // Wraps a value in quotes when it contains the delimiter.
+    pub(crate) fn quote(&self, value: &str) -> String {
+        let needs_quotes = match self {
+            Delimiter::Space => value.contains(char::is_whitespace),
+            Delimiter::Comma => value.contains(','),
+            Delimiter::Tab => value.contains('\t'),
+        };
+        if needs_quotes {
+            format!("\"{value}\"")
+        } else {
+            value.to_string()
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Delimiter::Space => " ",
+            Delimiter::Comma => ",",
+            Delimiter::Tab => "\t",
+        }
+    }
+}
+
+impl fmt::Display for Delimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Delimiter::Space => write!(f, "SPACE"),
+            Delimiter::Comma => write!(f, "COMMA"),
+            Delimiter::Tab => write!(f, "TAB"),
+        }
+    }
+}
+
+fn split_whitespace_quoted(raw: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut chars = raw.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            values.push(chars.by_ref().take_while(|&c| c != '"').collect());
+        } else {
+            let mut value = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() {
+                    break;
+                }
+                value.push(c);
+                chars.next();
+            }
+            values.push(value);
+        }
+    }
+    values
+}
+
 // ================================================================================================
 // ------------------------ Sink (trait) ----------------------------------------------------------
 // ================================================================================================
 
-pub(crate) trait Sink {
-    // Fires when we encounter a new section.
+/// Receives a LAS file piece by piece as it is parsed, see [`crate::stream`].
+///
+/// Events always arrive in this order:
+/// `start`, then for each section `section_start`, any number of `entry`/`ascii_row`, `section_end`, and finally `end`.
+/// Returning an error from any method stops parsing and that error is returned to the caller.
+pub trait Sink {
+    /// Fires when we encounter a new section. The section has no entries yet, they follow as
+    /// [`Sink::entry`] or [`Sink::ascii_row`] calls.
     fn section_start(&mut self, section: Section) -> Result<(), ParseError>;
-    // Fires when we encounter a section entry.
+    /// Fires when we encounter a section entry, eg. a data line in "~Well", a line of text
+    /// in "~Other", or a row of data in a LAS 3.0 section group like "~Core_Data".
     fn entry(&mut self, entry: SectionEntry) -> Result<(), ParseError>;
-    // Fires when we encounter an ascii data row.
+    /// Fires when we encounter a row in "~A", with one value per curve. Wrapped rows are
+    /// handed over once every line of the depth step has been read.
     fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError>;
-    // Fires when we are done parsing a section.
+    /// Fires when we are done parsing a section.
+    /// May also fire when no section is open, eg. before the first section.
     fn section_end(&mut self) -> Result<(), ParseError>;
-    // Fires when the parser starts parsing.
+    /// Fires when the parser starts parsing.
     fn start(&mut self) -> Result<(), ParseError> {
         Ok(())
     }
-    // Fires when the parser is done parsing.
+    /// Fires when the parser is done parsing.
     fn end(&mut self) -> Result<(), ParseError> {
         Ok(())
     }
@@ -159,29 +488,30 @@ pub(crate) trait Sink {
 // ================================================================================================
 
 #[derive(Debug, Serialize)]
-#[allow(dead_code)]
-pub(crate) struct Section {
+pub struct Section {
     pub header: SectionHeader,
+    /// Line number of the section header.
     pub line: usize,
+    /// Only filled in by sinks that collect entries themselves.
     pub entries: Vec<SectionEntry>,
+    /// Column mnemonics for "~A" and LAS 3.0 data sections, taken from the matching definition section.
     pub ascii_headers: Option<Vec<String>>,
+    /// Only filled in by sinks that collect rows themselves.
     pub ascii_rows: Vec<Vec<String>>,
+    /// Comments directly above the section header.
     pub comments: Option<Vec<String>>,
 }
 
 impl Section {
-    pub fn new(name: String, line: usize) -> Self {
-        Self {
-            header: SectionHeader {
-                kind: SectionKind::from(name.as_str()),
-                raw: name,
-            },
+    pub(crate) fn new(name: String, line: usize, las3: bool) -> Result<Self, ParseError> {
+        Ok(Self {
+            header: SectionHeader::parse(name, line, las3)?,
             line,
             entries: vec![],
             ascii_headers: None,
             ascii_rows: vec![],
             comments: None,
-        }
+        })
     }
 }
 
@@ -190,9 +520,12 @@ impl Section {
 // ================================================================================================
 
 #[derive(Debug, Serialize)]
-pub(crate) enum SectionEntry {
+pub enum SectionEntry {
+    /// "MNEM.UNIT VALUE : DESCRIPTION" lines.
     Delimited(DataLine),
+    /// A row of data in a LAS 3.0 section group.
     AsciiLogData(Vec<String>),
+    /// A line of free text in "~Other".
     Raw {
         text: String,
         comments: Option<Vec<String>>,
@@ -203,17 +536,42 @@ pub(crate) enum SectionEntry {
 // ------------------------ SectionHeader ---------------------------------------------------------
 // ================================================================================================
 
-#[derive(Debug, Serialize)]
-#[allow(dead_code)]
-pub(crate) struct SectionHeader {
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionHeader {
+    /// Header text without the leading '~', eg. "Curve Information Block".
     pub raw: String,
     pub kind: SectionKind,
+    // LAS 3.0 section group name, eg. "Core" for "~Core_Data".
+    pub group: Option<String>,
+    // LAS 3.0 association, eg. "Core_Definition" for "~Core_Data | Core_Definition".
+    pub association: Option<String>,
 }
 
-#[allow(dead_code)]
 impl SectionHeader {
     pub fn new(name: String, kind: SectionKind) -> Self {
-        Self { raw: name, kind }
+        Self {
+            raw: name,
+            kind,
+            group: None,
+            association: None,
+        }
+    }
+
+    pub(crate) fn parse(raw: String, line_number: usize, las3: bool) -> Result<Self, ParseError> {
+        let (kind, group) = SectionKind::classify(&raw, las3).ok_or_else(|| ParseError::UnrecognizedSection {
+            name: raw.clone(),
+            line_number,
+        })?;
+        let association = raw
+            .split_once('|')
+            .map(|(_, a)| a.trim().to_string())
+            .filter(|a| !a.is_empty());
+        Ok(Self {
+            raw,
+            kind,
+            group,
+            association,
+        })
     }
 }
 
@@ -223,9 +581,9 @@ impl SectionHeader {
 
 #[derive(Default)]
 pub(crate) struct LineDelimiters {
-    space: Option<usize>,
-    period: Option<usize>,
-    colon: Option<usize>,
+    pub space: Option<usize>,
+    pub period: Option<usize>,
+    pub colon: Option<usize>,
 }
 
 impl LineDelimiters {
@@ -0,0 +1,149 @@
+use crate::{
+    ParseError,
+    parse::{Section, SectionEntry, SectionKind, Sink, cell_json},
+    sections::{CurveInformation, ParameterInformation, VersionInformation, WellInformation},
+};
+use std::io::Write;
+
+/// Streams a LAS file as newline delimited JSON (one JSON document per line), without building a [`crate::LasFile`].
+///
+/// The first line is a header record with the Version, Well, Curve and Parameter sections, in the same shape as
+/// [`crate::JsonSink`]. Every line after that is one depth step, keyed by curve mnemonic, eg. `{"DEPT":1670.0,"GR":45.2}`.
+/// Values are written the same way as [`crate::JsonSink`], `null` for NULL.
+pub struct NdjsonSink<W>
+where
+    W: Write,
+{
+    writer: W,
+    current_section: Option<Section>,
+    // Sections of the header record, already written as JSON, held onto until the first row.
+    header: Vec<(&'static str, String)>,
+    wrote_header: bool,
+    // Each key already written as JSON, followed by a ':'.
+    keys: Vec<String>,
+    null: Option<f64>,
+}
+
+impl<W> NdjsonSink<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            current_section: None,
+            header: vec![],
+            wrote_header: false,
+            keys: vec![],
+            null: None,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn add_to_header<T>(&mut self, section_name: &'static str, section: &T) -> Result<(), ParseError>
+    where
+        T: serde::Serialize,
+    {
+        let json = serde_json::to_string(section).map_err(|e| ParseError::Error { message: e.to_string() })?;
+        self.header.push((section_name, json));
+        Ok(())
+    }
+
+    fn write_header(&mut self) -> Result<(), ParseError> {
+        if self.wrote_header {
+            return Ok(());
+        }
+        self.wrote_header = true;
+        write!(self.writer, "{{")?;
+        for (i, (section_name, json)) in std::mem::take(&mut self.header).into_iter().enumerate() {
+            if i != 0 {
+                write!(self.writer, ",")?;
+            }
+            write!(self.writer, "\"{section_name}\":{json}")?;
+        }
+        writeln!(self.writer, "}}")?;
+        Ok(())
+    }
+}
+
+impl<W> Sink for NdjsonSink<W>
+where
+    W: Write,
+{
+    fn end(&mut self) -> Result<(), ParseError> {
+        // Only when there is no ASCII log data.
+        self.write_header()?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
+        if section.header.kind == SectionKind::AsciiLogData {
+            self.write_header()?;
+            self.keys = section
+                .ascii_headers
+                .iter()
+                .flatten()
+                .map(|h| serde_json::to_string(h).map(|key| format!("{key}:")))
+                .collect::<Result<_, _>>()
+                .map_err(|e| ParseError::Error { message: e.to_string() })?;
+        }
+        self.current_section = Some(section);
+        Ok(())
+    }
+
+    fn entry(&mut self, entry: SectionEntry) -> Result<(), ParseError> {
+        if let Some(curr_sect) = self.current_section.as_mut() {
+            curr_sect.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+        write!(self.writer, "{{")?;
+        for (i, cell) in row.iter().enumerate() {
+            if i != 0 {
+                write!(self.writer, ",")?;
+            }
+            match self.keys.get(i) {
+                Some(key) => self.writer.write_all(key.as_bytes())?,
+                None => write!(self.writer, "\"{i}\":")?,
+            }
+            self.writer.write_all(cell_json(cell, self.null).as_bytes())?;
+        }
+        writeln!(self.writer, "}}")?;
+        Ok(())
+    }
+
+    fn section_end(&mut self) -> Result<(), ParseError> {
+        let Some(section) = self.current_section.take() else {
+            return Ok(());
+        };
+
+        match section.header.kind {
+            SectionKind::Well => {
+                let well = WellInformation::from_section(section)?;
+                self.null = well.null_value();
+                self.add_to_header("WellInformation", &well)?;
+            }
+            SectionKind::Version => {
+                self.add_to_header("VersionInformation", &VersionInformation::from_section(section)?)?
+            }
+            SectionKind::Curve => self.add_to_header("CurveInformation", &CurveInformation::try_from(section)?)?,
+            SectionKind::Parameter => {
+                self.add_to_header("ParameterInformation", &ParameterInformation::try_from(section)?)?
+            }
+            // Not part of the header record.
+            SectionKind::AsciiLogData
+            | SectionKind::Other
+            | SectionKind::GroupParameter
+            | SectionKind::GroupDefinition
+            | SectionKind::GroupData => {}
+        };
+
+        Ok(())
+    }
+}
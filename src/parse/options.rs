@@ -0,0 +1,43 @@
+use crate::{Diagnostic, Encoding, ParseError};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Stop at the first problem and return it as a [`ParseError`].
+    #[default]
+    Strict,
+    /// Keep going, fill in what we can and collect every problem as a [`Diagnostic`].
+    Lenient,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ParseOptions {
+    pub strictness: Strictness,
+    /// Check that ASCII log data agrees with "STRT", "STOP" and "STEP" in the Well section, and that the
+    /// index only moves one way. Off by default, every row has to be looked at.
+    pub validate_data: bool,
+    /// Text encoding of the file, see [`Encoding::Auto`].
+    pub encoding: Encoding,
+    /// Stop at the "~A" line, without reading any ASCII log data or the sections after it (LAS 3.0).
+    /// See [`crate::parse_header`].
+    pub stop_before_data: bool,
+}
+
+impl ParseOptions {
+    pub fn lenient() -> Self {
+        Self {
+            strictness: Strictness::Lenient,
+            ..Default::default()
+        }
+    }
+
+    // Strict mode hands the rule back as an error, lenient mode records it and carries on.
+    pub(crate) fn recover(&self, diagnostics: &mut Vec<Diagnostic>, diagnostic: Diagnostic) -> Result<(), ParseError> {
+        match self.strictness {
+            Strictness::Strict => Err(diagnostic.rule),
+            Strictness::Lenient => {
+                diagnostics.push(diagnostic);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    ParseError,
+    arrow::{header_metadata, log_data_schema, record_batch, to_parse_error},
+    parse::{Section, SectionEntry, SectionKind, Sink},
+    sections::{CurveInformation, ParameterInformation, VersionInformation, WellInformation},
+};
+use arrow_array::builder::Float64Builder;
+use arrow_schema::SchemaRef;
+use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+use std::io::Write;
+
+#[derive(Debug, Clone)]
+pub struct ParquetOptions {
+    /// Number of depth steps we hold in memory before writing them out as a row group.
+    pub row_group_size: usize,
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        Self { row_group_size: 65_536 }
+    }
+}
+
+/// Streams ASCII log data into a Parquet file, one nullable `Float64` column per curve.
+/// Rows are buffered until we have `row_group_size` of them, then written out as a row group.
+/// The schema carries the same metadata as [`crate::LasFile::to_record_batch`].
+pub struct ParquetSink<W>
+where
+    W: Write + Send,
+{
+    // Held until we reach "~A" and know the schema, and handed back once the file is closed.
+    writer: Option<W>,
+    arrow_writer: Option<ArrowWriter<W>>,
+    options: ParquetOptions,
+    current_section: Option<Section>,
+    version: VersionInformation,
+    well: WellInformation,
+    curves: CurveInformation,
+    parameters: Option<ParameterInformation>,
+    null: Option<f64>,
+    schema: Option<SchemaRef>,
+    columns: Vec<Float64Builder>,
+    buffered_rows: usize,
+}
+
+impl<W> ParquetSink<W>
+where
+    W: Write + Send,
+{
+    pub fn new(writer: W, options: ParquetOptions) -> Self {
+        Self {
+            writer: Some(writer),
+            arrow_writer: None,
+            options,
+            current_section: None,
+            version: VersionInformation::default(),
+            well: WellInformation::default(),
+            curves: CurveInformation::default(),
+            parameters: None,
+            null: None,
+            schema: None,
+            columns: vec![],
+            buffered_rows: 0,
+        }
+    }
+
+    /// Closes the Parquet file, unless [`Sink::end`] already has, and hands back the writer.
+    pub fn into_inner(mut self) -> Result<W, ParseError> {
+        if self.arrow_writer.is_some() {
+            self.end()?;
+        }
+        self.writer.take().ok_or_else(|| ParseError::Error {
+            message: "ParquetSink lost its writer".to_string(),
+        })
+    }
+
+    fn start_log_data(&mut self, headers: &[String]) -> Result<(), ParseError> {
+        let metadata = header_metadata(&self.version, &self.well, &self.curves, self.parameters.as_ref())?;
+        let schema = log_data_schema(headers, Some(&self.curves), metadata);
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(self.options.row_group_size)
+            .build();
+
+        let writer = self.writer.take().ok_or_else(|| ParseError::Error {
+            message: "ParquetSink can only write one file".to_string(),
+        })?;
+        let arrow_writer = ArrowWriter::try_new(writer, schema.clone(), Some(properties)).map_err(to_parse_error)?;
+
+        self.columns = headers
+            .iter()
+            .map(|_| Float64Builder::with_capacity(self.options.row_group_size))
+            .collect();
+        self.schema = Some(schema);
+        self.arrow_writer = Some(arrow_writer);
+        Ok(())
+    }
+
+    fn write_row_group(&mut self) -> Result<(), ParseError> {
+        let (Some(schema), Some(arrow_writer)) = (self.schema.clone(), self.arrow_writer.as_mut()) else {
+            return Ok(());
+        };
+        let columns = self.columns.iter_mut().map(|column| column.finish());
+        let batch = record_batch(schema, columns, self.buffered_rows)?;
+        arrow_writer.write(&batch).map_err(to_parse_error)?;
+        arrow_writer.flush().map_err(to_parse_error)?;
+        self.buffered_rows = 0;
+        Ok(())
+    }
+}
+
+impl<W> Sink for ParquetSink<W>
+where
+    W: Write + Send,
+{
+    fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
+        if section.header.kind == SectionKind::AsciiLogData {
+            return self.start_log_data(section.ascii_headers.as_deref().unwrap_or_default());
+        }
+        self.current_section = Some(section);
+        Ok(())
+    }
+
+    fn entry(&mut self, entry: SectionEntry) -> Result<(), ParseError> {
+        if let Some(section) = self.current_section.as_mut() {
+            section.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+        for (column, cell) in self.columns.iter_mut().zip(row) {
+            match cell.parse::<f64>() {
+                Ok(value) if Some(value) != self.null && !value.is_nan() => column.append_value(value),
+                _ => column.append_null(),
+            }
+        }
+        self.buffered_rows += 1;
+        if self.buffered_rows >= self.options.row_group_size {
+            self.write_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn section_end(&mut self) -> Result<(), ParseError> {
+        let Some(section) = self.current_section.take() else {
+            return Ok(());
+        };
+        match section.header.kind {
+            SectionKind::Version => self.version = VersionInformation::from_section(section)?,
+            SectionKind::Well => {
+                self.well = WellInformation::from_section(section)?;
+                self.null = self.well.null_value();
+            }
+            SectionKind::Curve => self.curves = CurveInformation::try_from(section)?,
+            SectionKind::Parameter => self.parameters = Some(ParameterInformation::try_from(section)?),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        // No log data (eg. LAS 3.0), still write a valid file.
+        if self.arrow_writer.is_none() {
+            self.start_log_data(&[])?;
+        }
+        if self.buffered_rows > 0 {
+            self.write_row_group()?;
+        }
+        if let Some(arrow_writer) = self.arrow_writer.take() {
+            self.writer = Some(arrow_writer.into_inner().map_err(to_parse_error)?);
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
-    InvalidLineKind, ParseError, Section, SectionEntry, SectionKind,
+    Diagnostic, InvalidLineKind, ParseError, Section, SectionEntry, SectionKind, Severity,
     parse::{
-        DataLine, LasValue, LineDelimiters, REQUIRED_SECTIONS, Sink, context::ParserContext, state::ParserState,
-        str_contains,
+        DataLine, Delimiter, DepthCheck, LasValue, LineDelimiters, ParseOptions, REQUIRED_LAS3_SECTIONS,
+        REQUIRED_SECTIONS, SectionHeader, Sink, context::ParserContext, state::ParserState, str_contains,
+        validate_curve_mnemonics,
     },
+    sections::{VersionInformation, WellInformation},
     tokenizer::LasToken,
 };
 use std::io;
 
+// Used for missing values when the Well section has no "NULL", eg. to pad short rows in lenient mode.
+pub(crate) const DEFAULT_NULL: &str = "-999.25";
+
 pub(crate) struct LasParser<I>
 where
     I: Iterator<Item = Result<LasToken, io::Error>>,
@@ -27,6 +32,25 @@ where
         }
     }
 
+    pub fn with_options(iter: I, options: ParseOptions) -> Self {
+        Self {
+            tokens: iter,
+            ctx: ParserContext {
+                options,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn tokens(&self) -> &I {
+        &self.tokens
+    }
+
+    /// Problems recovered from while parsing in lenient mode.
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.ctx.diagnostics)
+    }
+
     pub fn parse_into<S>(&mut self, sink: &mut S) -> Result<(), ParseError>
     where
         S: Sink,
@@ -34,10 +58,18 @@ where
         sink.start()?;
         while let Some(token) = self.next_token()? {
             self.handle_token(token, sink)?;
+            if self.stopped_before_data() {
+                break;
+            }
         }
         self.finish(sink)
     }
 
+    /// Whether parsing stopped at the "~A" line, see [`ParseOptions::stop_before_data`].
+    pub fn stopped_before_data(&self) -> bool {
+        self.ctx.options.stop_before_data && self.ctx.state == ParserState::In(SectionKind::AsciiLogData)
+    }
+
     fn handle_token<S>(&mut self, token: LasToken, sink: &mut S) -> Result<(), ParseError>
     where
         S: Sink,
@@ -54,13 +86,44 @@ where
     where
         S: Sink,
     {
-        let mut next_section = Section::new(name, line_number);
+        self.validate_header_section()?;
+
+        let raw = format!("~{name}");
+        let mut next_section = match Section::new(name, line_number, self.ctx.las3) {
+            Ok(section) => section,
+            Err(e) => {
+                self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, &raw))?;
+                return self.skip_section(sink);
+            }
+        };
         next_section.comments = self.ctx.comments.take();
 
-        self.enter_section(&next_section.header.kind, line_number)?;
+        if !self.enter_section(&next_section.header, line_number, &raw)? {
+            return self.skip_section(sink);
+        }
 
-        if next_section.header.kind == SectionKind::AsciiLogData {
-            next_section.ascii_headers = Some(self.ctx.curve_mnemonics.clone());
+        match next_section.header.kind {
+            SectionKind::AsciiLogData => {
+                next_section.ascii_headers = Some(self.ctx.curve_mnemonics.clone());
+            }
+            SectionKind::GroupData => match self.group_data_headers(&next_section.header) {
+                Ok(headers) => next_section.ascii_headers = Some(headers),
+                Err(e) => {
+                    self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, &raw))?;
+                    return self.skip_section(sink);
+                }
+            },
+            SectionKind::Version | SectionKind::Well => {
+                self.ctx.header_section = Some(Section {
+                    header: next_section.header.clone(),
+                    line: line_number,
+                    entries: vec![],
+                    ascii_headers: None,
+                    ascii_rows: vec![],
+                    comments: None,
+                });
+            }
+            _ => {}
         }
 
         sink.section_end()?;
@@ -68,14 +131,108 @@ where
         Ok(())
     }
 
-    fn enter_section(&mut self, section: &SectionKind, line_number: usize) -> Result<(), ParseError> {
-        self.validate_transition(section, line_number)?;
-        self.validate_duplicates(section, line_number)?;
-        self.ctx.sections.insert(*section, line_number);
+    // Lenient mode only, ignore everything up to the next section header.
+    fn skip_section<S>(&mut self, sink: &mut S) -> Result<(), ParseError>
+    where
+        S: Sink,
+    {
+        self.ctx.skipping = true;
+        sink.section_end()
+    }
+
+    // Returns false if the section should be skipped.
+    fn enter_section(&mut self, header: &SectionHeader, line_number: usize, raw: &str) -> Result<bool, ParseError> {
+        let section = &header.kind;
+        self.ctx.skipping = false;
+
+        match self.validate_transition(section, line_number) {
+            // A second Version section is reported as a duplicate below.
+            Ok(()) | Err(ParseError::DuplicateSection { .. }) => {}
+            Err(e) => self.recover(Diagnostic::new(Severity::Warning, e).on_line(line_number, raw))?,
+        }
+
+        // LAS 3.0 section groups may repeat the same kind of section, eg. "~Core_Parameter"
+        // and "~Tops_Parameter", so we track them by title instead of by kind.
+        let duplicate = if header.group.is_some() {
+            self.validate_group_duplicates(header, line_number)
+        } else {
+            self.validate_duplicates(section, line_number)
+        };
+        // We keep the first one.
+        if let Err(e) = duplicate {
+            self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+            return Ok(false);
+        }
+
+        self.ctx.current_group = header.group.as_ref().map(|g| g.to_lowercase());
+        self.ctx.sections.entry(*section).or_insert(line_number);
         self.ctx.state = ParserState::In(*section);
+        Ok(true)
+    }
+
+    fn recover(&mut self, diagnostic: Diagnostic) -> Result<(), ParseError> {
+        self.ctx.options.recover(&mut self.ctx.diagnostics, diagnostic)
+    }
+
+    // Checks the contents of the Version or Well section we just left. Problems are reported on the line
+    // of the data line at fault when there is one, or the section header otherwise.
+    fn validate_header_section(&mut self) -> Result<(), ParseError> {
+        let Some(section) = self.ctx.header_section.take() else {
+            return Ok(());
+        };
+
+        match section.header.kind {
+            SectionKind::Version => {
+                let version = VersionInformation::from_section(section)?;
+                if let Err(e) = version.validate() {
+                    self.recover(Diagnostic::new(Severity::Error, e).on_line(version.line_number, &version.header))?;
+                }
+            }
+            SectionKind::Well => {
+                let well = WellInformation::from_section(section)?;
+                for e in well.validation_errors() {
+                    // Missing one-of data lines like "UWI" or "PROV" lose nothing.
+                    let severity = match e {
+                        ParseError::SectionMissingRequiredData { .. } => Severity::Warning,
+                        _ => Severity::Error,
+                    };
+                    let (line_number, line) = offending_mnemonic(&e)
+                        .and_then(|mnemonic| self.ctx.well_lines.get(mnemonic))
+                        .map_or((well.line_number, well.header.clone()), |(n, raw)| (*n, raw.clone()));
+                    self.recover(Diagnostic::new(severity, e).on_line(line_number, &line))?;
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 
+    // Column mnemonics for a LAS 3.0 group data section come from the definition section
+    // named in its association ("~Core_Data | Core_Definition"), or its own group otherwise.
+    fn group_data_headers(&mut self, header: &SectionHeader) -> Result<Vec<String>, ParseError> {
+        let group = header
+            .association
+            .as_deref()
+            .and_then(|a| SectionKind::classify(a, true))
+            .and_then(|(_, group)| group)
+            .or_else(|| header.group.clone())
+            .unwrap_or_default();
+
+        let key = group.to_lowercase();
+        let mnemonics =
+            self.ctx
+                .group_mnemonics
+                .get(&key)
+                .cloned()
+                .ok_or_else(|| ParseError::SectionMissingRequiredData {
+                    section: SectionKind::GroupDefinition,
+                    one_of: vec![format!("{group}_Definition")],
+                })?;
+
+        self.ctx.current_group = Some(key);
+        Ok(mnemonics)
+    }
+
     fn validate_transition(&mut self, section: &SectionKind, line_number: usize) -> Result<(), ParseError> {
         match (&self.ctx.state, section) {
             // Version must be first
@@ -94,7 +251,8 @@ where
                 Err(ParseError::VersionInformationNotFirst { line_number })
             }
             // Transitioning from Ascii section to anything means Ascii wasn't last.
-            (ParserState::In(SectionKind::AsciiLogData), _) => {
+            // LAS 3.0 allows other section groups to follow log data.
+            (ParserState::In(SectionKind::AsciiLogData), _) if !self.ctx.las3 => {
                 Err(ParseError::AsciiLogDataSectionNotLast { line_number })
             }
             (ParserState::In(_), _) => Ok(()),
@@ -113,21 +271,72 @@ where
     where
         S: Sink,
     {
+        if self.ctx.skipping {
+            return Ok(());
+        }
+
         let entry = match self.ctx.state {
             ParserState::In(SectionKind::Other) => SectionEntry::Raw {
                 text: raw.trim().to_string(),
                 comments: self.ctx.comments.take(),
             },
-            ParserState::In(SectionKind::AsciiLogData) => self.parse_ascii_data_line(raw, line_number)?,
-            _ => self.parse_data_line(raw, line_number)?,
+            ParserState::In(SectionKind::AsciiLogData) => match self.parse_ascii_data_line(raw, line_number)? {
+                Some(entry) => entry,
+                // We are in the middle of a wrapped depth step (or skipped the line), keep reading.
+                None => return Ok(()),
+            },
+            ParserState::In(SectionKind::GroupData) => self.parse_group_data_line(raw, line_number)?,
+            _ => match self.parse_data_line(raw, line_number) {
+                Ok(entry) => entry,
+                Err(e) => {
+                    self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+                    return Ok(());
+                }
+            },
         };
 
         match entry {
-            SectionEntry::AsciiLogData(ref row) => sink.ascii_row(row)?,
-            SectionEntry::Raw { .. } => sink.entry(entry)?,
+            // Log data is streamed to the sink row by row. Data in other LAS 3.0 section
+            // groups is small, so it is handed over as regular entries.
+            SectionEntry::AsciiLogData(ref row) if self.ctx.state == ParserState::In(SectionKind::AsciiLogData) => {
+                if self.ctx.options.validate_data {
+                    self.check_depth(row, line_number, raw)?;
+                }
+                sink.ascii_row(row)?
+            }
+            SectionEntry::AsciiLogData(_) | SectionEntry::Raw { .. } => sink.entry(entry)?,
             SectionEntry::Delimited(ref data_line) => {
-                if self.ctx.state == ParserState::In(SectionKind::Curve) {
-                    self.ctx.curve_mnemonics.push(data_line.mnemonic.clone());
+                if let Some(section) = self.ctx.header_section.as_mut() {
+                    section.entries.push(SectionEntry::Delimited(data_line.clone()));
+                }
+                match self.ctx.state {
+                    ParserState::In(SectionKind::Curve) => {
+                        if self.ctx.first_curve_line.is_none() {
+                            self.ctx.first_curve_line = Some((line_number, raw.to_string()));
+                        }
+                        self.ctx.curve_mnemonics.push(data_line.mnemonic.clone());
+                    }
+                    ParserState::In(SectionKind::Well) => {
+                        let mnemonic = data_line.mnemonic.to_ascii_uppercase();
+                        match mnemonic.as_str() {
+                            "NULL" => self.ctx.null_text = data_line.value.as_ref().map(|v| v.to_string()),
+                            "STRT" => self.ctx.start = data_line.value.clone(),
+                            "STOP" => self.ctx.stop = data_line.value.clone(),
+                            "STEP" => self.ctx.step = data_line.value.clone(),
+                            _ => {}
+                        }
+                        self.ctx
+                            .well_lines
+                            .entry(mnemonic)
+                            .or_insert_with(|| (line_number, raw.to_string()));
+                    }
+                    ParserState::In(SectionKind::GroupDefinition) => {
+                        let group = self.ctx.current_group.clone().unwrap_or_default();
+                        let mnemonics = self.ctx.group_mnemonics.entry(group).or_default();
+                        mnemonics.push(data_line.mnemonic.clone());
+                    }
+                    ParserState::In(SectionKind::Version) => self.handle_version_line(data_line, line_number, raw)?,
+                    _ => {}
                 }
                 sink.entry(entry)?;
             }
@@ -136,24 +345,90 @@ where
         Ok(())
     }
 
+    // Checks the depth of a row of ASCII log data against the Well section, see `ParseOptions::validate_data`.
+    fn check_depth(&mut self, row: &[String], line_number: usize, raw: &str) -> Result<(), ParseError> {
+        let Some(depth) = row.first() else {
+            return Ok(());
+        };
+        // A wrapped depth step started a few lines up, on a line of its own.
+        let (line_number, raw) = match self.ctx.wrap {
+            true => (self.ctx.wrapped_row_line_number, depth.as_str()),
+            false => (line_number, raw),
+        };
+        let ctx = &mut self.ctx;
+        let check = ctx
+            .depth_check
+            .get_or_insert_with(|| DepthCheck::new(ctx.start.as_ref(), ctx.stop.as_ref(), ctx.step.as_ref()));
+        for e in check.row(depth) {
+            self.recover(Diagnostic::new(Severity::Warning, e).on_line(line_number, raw))?;
+        }
+        self.ctx.last_row_line = Some((line_number, raw.to_string()));
+        Ok(())
+    }
+
+    fn handle_version_line(&mut self, data_line: &DataLine, line_number: usize, raw: &str) -> Result<(), ParseError> {
+        let value = data_line.value.as_ref().map(|v| v.to_string()).unwrap_or_default();
+        match data_line.mnemonic.to_ascii_uppercase().as_str() {
+            "VERS" => self.ctx.las3 = value.starts_with('3'),
+            "WRAP" => self.ctx.wrap = value.eq_ignore_ascii_case("YES"),
+            "DLM" => match Delimiter::parse(&value) {
+                Some(delimiter) => self.ctx.delimiter = delimiter,
+                None => {
+                    let e = ParseError::Error {
+                        message: format!(
+                            "Unrecognized DLM {value:?} on line {line_number}! Expected one of SPACE, COMMA or TAB."
+                        ),
+                    };
+                    // Falls back to SPACE
+                    self.recover(Diagnostic::new(Severity::Warning, e).on_line(line_number, raw))?;
+                }
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_comment(&mut self, text: String, line_number: usize) -> Result<(), ParseError> {
-        if self.ctx.state == ParserState::In(SectionKind::AsciiLogData) {
-            return Err(ParseError::AsciiDataContainsInvalidLine {
+        if self.ctx.state == ParserState::In(SectionKind::AsciiLogData) && !self.ctx.skipping {
+            let e = ParseError::AsciiDataContainsInvalidLine {
                 line_number,
                 line_kind: InvalidLineKind::Comment,
-            });
+            };
+            return self.recover(Diagnostic::new(Severity::Warning, e).on_line(line_number, &text));
+        }
+        if self.ctx.skipping {
+            return Ok(());
         }
         self.ctx.comments.push(text);
         Ok(())
     }
 
     fn handle_blank(&mut self, line_number: usize) -> Result<(), ParseError> {
-        if self.ctx.state == ParserState::In(SectionKind::AsciiLogData) {
-            return Err(ParseError::AsciiDataContainsInvalidLine {
+        if self.ctx.state == ParserState::In(SectionKind::AsciiLogData) && !self.ctx.skipping {
+            let e = ParseError::AsciiDataContainsInvalidLine {
                 line_number,
                 line_kind: InvalidLineKind::Empty,
+            };
+            return self.recover(Diagnostic::new(Severity::Warning, e).on_line(line_number, ""));
+        }
+        Ok(())
+    }
+
+    fn validate_group_duplicates(&mut self, header: &SectionHeader, line_number: usize) -> Result<(), ParseError> {
+        let title = header
+            .raw
+            .split(|c: char| c.is_whitespace() || c == '|')
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(&duplicate_line_number) = self.ctx.group_sections.get(&title) {
+            return Err(ParseError::DuplicateSection {
+                section: header.kind,
+                line_number,
+                duplicate_line_number,
             });
         }
+        self.ctx.group_sections.insert(title, line_number);
         Ok(())
     }
 
@@ -168,12 +443,18 @@ where
         Ok(())
     }
 
-    fn check_for_required_sections(&self) -> Result<(), ParseError> {
-        for required_section in REQUIRED_SECTIONS.iter() {
+    fn check_for_required_sections(&mut self) -> Result<(), ParseError> {
+        let required_sections: &[SectionKind] = if self.ctx.las3 {
+            &REQUIRED_LAS3_SECTIONS
+        } else {
+            &REQUIRED_SECTIONS
+        };
+        for required_section in required_sections.iter() {
             if !self.ctx.sections.contains_key(required_section) {
-                return Err(ParseError::MissingSection {
+                let e = ParseError::MissingSection {
                     section: *required_section,
-                });
+                };
+                self.recover(Diagnostic::new(Severity::Error, e))?;
             }
         }
         Ok(())
@@ -183,9 +464,38 @@ where
     where
         S: Sink,
     {
+        self.validate_header_section()?;
         self.check_for_required_sections()?;
         self.validate_curves()?;
 
+        // File ended part way through a wrapped depth step.
+        if !self.ctx.wrapped_row.is_empty() {
+            let num_curves = self.ctx.curve_mnemonics.len();
+            let e = ParseError::AsciiColumnsMismatch {
+                line_number: self.ctx.wrapped_row_line_number,
+                num_cols_in_headers: num_curves,
+                num_cols_in_row: self.ctx.wrapped_row.len(),
+            };
+            self.recover(Diagnostic::new(Severity::Error, e))?;
+            let mut row = std::mem::take(&mut self.ctx.wrapped_row);
+            self.fit_row(&mut row, num_curves);
+            sink.ascii_row(&row)?;
+        }
+
+        if let Some(check) = self.ctx.depth_check.take() {
+            let (stop, undeclared_step) = check.finish();
+            if let Some(e) = stop {
+                let mut diagnostic = Diagnostic::new(Severity::Warning, e);
+                if let Some((line_number, raw)) = self.ctx.last_row_line.take() {
+                    diagnostic = diagnostic.on_line(line_number, &raw);
+                }
+                self.recover(diagnostic)?;
+            }
+            if let Some(e) = undeclared_step {
+                self.recover(Diagnostic::new(Severity::Warning, e))?;
+            }
+        }
+
         if let ParserState::In(_) = self.ctx.state {
             sink.section_end()?;
         }
@@ -224,7 +534,16 @@ where
         // "description or definition of the mnemonic. It is always located to the right
         // of the last colon. The length of the line is no longer limited."
 
-        let ld = LineDelimiters::find_in(raw);
+        // LAS 3.0 lines may end with a "{format}" and/or "| association", eg.
+        // "BHT .DEGC 35.5 : BOTTOM HOLE TEMPERATURE {F} | Run[1]"
+        // We strip those off so the rest of the line can be handled like LAS 2.0
+        let (line, format, association) = if self.ctx.las3 {
+            Self::split_las3_data_line(raw)
+        } else {
+            (raw, None, None)
+        };
+
+        let ld = LineDelimiters::find_in(line);
 
         let mut mnemonic: Option<String> = None;
         let mut unit: Option<String> = None;
@@ -232,14 +551,14 @@ where
         let mut description: Option<String> = None;
 
         if let Some(period_index) = ld.period {
-            let raw_mnemonic = raw[..period_index].trim().to_string();
+            let raw_mnemonic = line[..period_index].trim().to_string();
             Self::validate_mnemonic(&raw_mnemonic, raw, line_number)?;
             mnemonic = Some(raw_mnemonic);
 
             if let Some(space_index) = ld.space
                 && let Some(colon_index) = ld.colon
             {
-                let raw_unit = raw[period_index..space_index].trim_start_matches('.').to_string();
+                let raw_unit = line[period_index..space_index].trim_start_matches('.').to_string();
                 Self::validate_unit(&raw_unit, raw, line_number)?;
                 unit = Some(raw_unit);
 
@@ -251,17 +570,19 @@ where
                     });
                 }
 
-                value = LasValue::parse(&raw[space_index..colon_index]);
+                value = LasValue::parse(&line[space_index..colon_index]);
             }
         }
 
         if let Some(colon_index) = ld.colon {
             // Everything from the last recorded colon index until end of line is description.
-            description = Some(raw[colon_index + 1..raw.len()].trim().to_string());
+            description = Some(line[colon_index + 1..line.len()].trim().to_string());
         }
 
         Ok(SectionEntry::Delimited(DataLine {
             comments: self.ctx.comments.take(),
+            format,
+            association,
             value,
             unit: unit.filter(|u| !u.is_empty()),
             description: description.filter(|d| !d.is_empty()),
@@ -273,30 +594,125 @@ where
         }))
     }
 
-    fn parse_ascii_data_line(&mut self, raw: &str, line_number: usize) -> Result<SectionEntry, ParseError> {
+    fn split_las3_data_line(raw: &str) -> (&str, Option<String>, Option<String>) {
+        let (mut line, association) = match raw.rfind('|') {
+            Some(i) => (&raw[..i], Some(raw[i + 1..].trim().to_string())),
+            None => (raw, None),
+        };
+
+        let mut format = None;
+        let trimmed = line.trim_end();
+        if let Some(without_brace) = trimmed.strip_suffix('}')
+            && let Some(open) = without_brace.rfind('{')
+        {
+            format = Some(without_brace[open + 1..].trim().to_string());
+            line = &without_brace[..open];
+        }
+
+        (
+            line,
+            format.filter(|f| !f.is_empty()),
+            association.filter(|a| !a.is_empty()),
+        )
+    }
+
+    fn parse_ascii_data_line(&mut self, raw: &str, line_number: usize) -> Result<Option<SectionEntry>, ParseError> {
         // If we are missing headers here it means we haven't parsed the Curve section yet.
         // Since ASCII section has to be the last section (per CWLS v2.0) it means we have
         // and invalid LAS file.
         if self.ctx.curve_mnemonics.is_empty() {
-            return Err(ParseError::AsciiLogDataSectionNotLast { line_number });
+            let e = ParseError::AsciiLogDataSectionNotLast { line_number };
+            self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+            return Ok(None);
+        }
+
+        if self.ctx.wrap {
+            return self.parse_wrapped_ascii_data_line(raw, line_number);
+        }
+
+        let mut values = if self.ctx.las3 {
+            self.ctx.delimiter.split(raw)
+        } else {
+            let mut values = Vec::with_capacity(self.ctx.curve_mnemonics.len());
+            for token in raw.split_ascii_whitespace() {
+                values.push(token.to_string());
+            }
+            values
+        };
+
+        let num_curves = self.ctx.curve_mnemonics.len();
+        if values.len() != num_curves {
+            let e = ParseError::AsciiColumnsMismatch {
+                line_number,
+                num_cols_in_headers: num_curves,
+                num_cols_in_row: values.len(),
+            };
+            self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+            self.fit_row(&mut values, num_curves);
+        }
+
+        Ok(Some(SectionEntry::AsciiLogData(values)))
+    }
+
+    // "WRAP. YES" means a single depth step may span multiple lines. We collect values across
+    // lines until we have one value per curve, and only then hand back a complete row.
+    fn parse_wrapped_ascii_data_line(
+        &mut self,
+        raw: &str,
+        line_number: usize,
+    ) -> Result<Option<SectionEntry>, ParseError> {
+        let num_curves = self.ctx.curve_mnemonics.len();
+
+        if self.ctx.wrapped_row.is_empty() {
+            self.ctx.wrapped_row_line_number = line_number;
         }
 
-        let mut values = Vec::with_capacity(self.ctx.curve_mnemonics.len());
         for token in raw.split_ascii_whitespace() {
-            values.push(token.to_string());
+            self.ctx.wrapped_row.push(token.to_string());
+        }
+
+        if self.ctx.wrapped_row.len() > num_curves {
+            let e = ParseError::AsciiColumnsMismatch {
+                line_number: self.ctx.wrapped_row_line_number,
+                num_cols_in_headers: num_curves,
+                num_cols_in_row: self.ctx.wrapped_row.len(),
+            };
+            self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+            self.ctx.wrapped_row.truncate(num_curves);
+        }
+
+        if self.ctx.wrapped_row.len() < num_curves {
+            return Ok(None);
         }
 
-        if values.len() != self.ctx.curve_mnemonics.len() {
-            return Err(ParseError::AsciiColumnsMismatch {
+        let values = std::mem::replace(&mut self.ctx.wrapped_row, Vec::with_capacity(num_curves));
+        Ok(Some(SectionEntry::AsciiLogData(values)))
+    }
+
+    fn parse_group_data_line(&mut self, raw: &str, line_number: usize) -> Result<SectionEntry, ParseError> {
+        let group = self.ctx.current_group.as_deref().unwrap_or_default();
+        let num_cols_in_headers = self.ctx.group_mnemonics.get(group).map(|m| m.len()).unwrap_or_default();
+        let mut values = self.ctx.delimiter.split(raw);
+
+        if values.len() != num_cols_in_headers {
+            let e = ParseError::AsciiColumnsMismatch {
                 line_number,
-                num_cols_in_headers: self.ctx.curve_mnemonics.len(),
+                num_cols_in_headers,
                 num_cols_in_row: values.len(),
-            });
+            };
+            self.recover(Diagnostic::new(Severity::Error, e).on_line(line_number, raw))?;
+            self.fit_row(&mut values, num_cols_in_headers);
         }
 
         Ok(SectionEntry::AsciiLogData(values))
     }
 
+    // Lenient mode only, pads a short row with NULL or drops the extra values of a long one.
+    fn fit_row(&self, values: &mut Vec<String>, num_cols: usize) {
+        let null = self.ctx.null_text.as_deref().unwrap_or(DEFAULT_NULL);
+        values.resize(num_cols, null.to_string());
+    }
+
     pub(crate) fn validate_mnemonic(raw_mnemonic: &str, raw: &str, line_number: usize) -> Result<(), ParseError> {
         if raw_mnemonic.is_empty() {
             return Err(ParseError::MissingRequiredKey {
@@ -338,29 +754,32 @@ where
         Ok(())
     }
 
-    fn validate_curves(&self) -> Result<(), ParseError> {
-        let allowed_first_curves = [
-            "DEPT".to_string(),
-            "DEPTH".to_string(),
-            "TIME".to_string(),
-            "INDEX".to_string(),
-        ];
-
-        if self.ctx.curve_mnemonics.is_empty() {
-            return Err(ParseError::SectionMissingRequiredData {
-                section: SectionKind::Curve,
-                one_of: Vec::from(allowed_first_curves),
-            });
+    fn validate_curves(&mut self) -> Result<(), ParseError> {
+        // LAS 3.0 files without log data have no curves to validate.
+        if self.ctx.las3 && !self.ctx.sections.contains_key(&SectionKind::Curve) {
+            return Ok(());
         }
 
-        // The first data line in the Curve section must be one of "DEPT", "DEPTH", "TIME" or "INDEX".
-        if !allowed_first_curves.contains(&self.ctx.curve_mnemonics[0]) {
-            return Err(ParseError::DisallowedFirstCurve {
-                got: self.ctx.curve_mnemonics[0].clone(),
-                expected_one_of: Vec::from(allowed_first_curves),
-            });
+        match validate_curve_mnemonics(&self.ctx.curve_mnemonics) {
+            Ok(()) => Ok(()),
+            Err(e @ ParseError::DisallowedFirstCurve { .. }) => {
+                let mut diagnostic = Diagnostic::new(Severity::Warning, e);
+                if let Some((line_number, line)) = &self.ctx.first_curve_line {
+                    diagnostic = diagnostic.on_line(*line_number, line);
+                }
+                self.recover(diagnostic)
+            }
+            Err(e) => self.recover(Diagnostic::new(Severity::Error, e)),
         }
+    }
+}
 
-        Ok(())
+// The Well section data line a validation error is about, if it is about one.
+fn offending_mnemonic(e: &ParseError) -> Option<&str> {
+    match e {
+        ParseError::WellDataMissingRequiredValueForMnemonic { mnemonic }
+        | ParseError::InvalidWellValue { mnemonic, .. } => Some(mnemonic),
+        ParseError::InconsistentStep { .. } => Some("STEP"),
+        _ => None,
     }
 }
@@ -1,7 +1,9 @@
 use crate::{
     ParseError,
     parse::{Section, SectionEntry, SectionKind, Sink},
-    sections::{CurveInformation, OtherInformation, ParameterInformation, VersionInformation, WellInformation},
+    sections::{
+        CurveInformation, OtherInformation, ParameterInformation, SectionGroup, VersionInformation, WellInformation,
+    },
 };
 use serde::Serialize;
 use std::io::Write;
@@ -9,12 +11,15 @@ use std::io::Write;
 // We store every section outside of AsciiLogData within the 'current_section'.
 // Those sections are very small in comparison to ascii data. We directly stream
 // and write the ascii data to the writer, no allocations or buffering.
-pub(crate) struct YamlSink<W>
+/// Streams a LAS file as YAML, without building a [`crate::LasFile`].
+pub struct YamlSink<W>
 where
     W: Write,
 {
     writer: W,
     current_section: Option<Section>,
+    // LAS 3.0 section groups can come before or after log data, so we hold onto them until the end.
+    section_groups: Vec<SectionGroup>,
 }
 
 impl<W> YamlSink<W>
@@ -25,6 +30,7 @@ where
         Self {
             writer,
             current_section: None,
+            section_groups: vec![],
         }
     }
 
@@ -53,6 +59,14 @@ impl<W> Sink for YamlSink<W>
 where
     W: Write,
 {
+    fn end(&mut self) -> Result<(), ParseError> {
+        if !self.section_groups.is_empty() {
+            let section_groups = std::mem::take(&mut self.section_groups);
+            self.write_section("SectionGroups", &section_groups)?;
+        }
+        Ok(())
+    }
+
     fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
         if section.header.kind == SectionKind::AsciiLogData {
             writeln!(self.writer, "AsciiLogData:")?;
@@ -93,11 +107,11 @@ where
             let kind = section.header.kind;
 
             match kind {
-                SectionKind::Well => self.write_section("WellInformation", &WellInformation::try_from(section)?)?,
+                SectionKind::Well => self.write_section("WellInformation", &WellInformation::from_section(section)?)?,
                 SectionKind::Curve => self.write_section("CurveInformation", &CurveInformation::try_from(section)?)?,
                 SectionKind::Other => self.write_section("OtherInformation", &OtherInformation::try_from(section)?)?,
                 SectionKind::Version => {
-                    self.write_section("VersionInformation", &VersionInformation::try_from(section)?)?;
+                    self.write_section("VersionInformation", &VersionInformation::from_section(section)?)?;
                 }
                 SectionKind::Parameter => {
                     self.write_section("ParameterInformation", &ParameterInformation::try_from(section)?)?
@@ -113,6 +127,9 @@ where
                     }
                     writeln!(self.writer, "  header: ~{}", section.header.raw)?;
                 }
+                SectionKind::GroupParameter | SectionKind::GroupDefinition | SectionKind::GroupData => {
+                    SectionGroup::add_section(&mut self.section_groups, section)?;
+                }
             };
         }
 
@@ -0,0 +1,247 @@
+use crate::{
+    LasFile, LasValue, ParseError, decimal_places,
+    parse::DEFAULT_NULL,
+    sections::{AsciiLogData, AsciiLogDataParams, Curve},
+};
+
+// Depths closer than this are the same depth, so float error doesn't push a depth out of range.
+pub(crate) const DEPTH_EPSILON: f64 = 1e-9;
+
+// Enough for any real depth, and keeps float error like 0.30000000000000004 out of the index.
+const MAX_DEPTH_DECIMALS: usize = 6;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ResampleMethod {
+    /// Value of the closest depth.
+    Nearest,
+    /// Straight line between the depths on either side.
+    #[default]
+    Linear,
+    /// Average of every value within half a step of the new depth. Meant for moving to a larger step.
+    BlockAverage,
+}
+
+impl AsciiLogData {
+    /// Resamples every curve onto `depths`, which become the new index.
+    ///
+    /// NULL values are never interpolated. With [`ResampleMethod::Linear`], a new depth next to a NULL is NULL,
+    /// while [`ResampleMethod::BlockAverage`] only averages the values that aren't NULL.
+    /// Depths outside of the original index are NULL, we don't extrapolate. Without a NULL value, -999.25 is used.
+    pub fn resample(&self, depths: &[f64], method: ResampleMethod) -> AsciiLogData {
+        let null = self.index().and_then(Curve::null_value);
+        let null_text = null.map_or_else(|| DEFAULT_NULL.to_string(), |n| n.to_string());
+        self.resample_with_null(depths, method, &null_text)
+    }
+
+    // Same as `resample`, writing NULL values as `null_text`, eg. the text of the Well section "NULL".
+    fn resample_with_null(&self, depths: &[f64], method: ResampleMethod, null_text: &str) -> AsciiLogData {
+        let Some(index) = self.index() else {
+            return self.resampled(vec![]);
+        };
+
+        // (depth, row) sorted by depth, so we can binary search whichever way the index goes.
+        let mut samples: Vec<(f64, usize)> = index.iter().enumerate().filter_map(|(i, d)| Some((d?, i))).collect();
+        samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // As precise as the original index or the new depths, whichever is more.
+        let depth_decimals = depths
+            .iter()
+            .map(|d| decimal_places(&d.to_string()).min(MAX_DEPTH_DECIMALS))
+            .chain([curve_decimals(index)])
+            .max()
+            .unwrap_or_default();
+
+        // Interpolated values fall between depths, so they are at least as precise as the index.
+        // Nearest values are copied as they are.
+        let decimals: Vec<usize> = self
+            .curves
+            .iter()
+            .map(|curve| match method {
+                ResampleMethod::Nearest => curve_decimals(curve),
+                ResampleMethod::Linear | ResampleMethod::BlockAverage => curve_decimals(curve).max(depth_decimals),
+            })
+            .collect();
+
+        let mut rows = Vec::with_capacity(depths.len());
+        for (i, &depth) in depths.iter().enumerate() {
+            let mut row = Vec::with_capacity(self.curves.len());
+            row.push(format!("{depth:.depth_decimals$}"));
+
+            let block = block_bounds(depths, i);
+            for (curve, &decimals) in self.curves.iter().zip(&decimals).skip(1) {
+                let value = match method {
+                    ResampleMethod::Nearest => nearest(&samples, curve, depth),
+                    ResampleMethod::Linear => linear(&samples, curve, depth),
+                    ResampleMethod::BlockAverage => block_average(&samples, curve, depth, block),
+                };
+                row.push(match value {
+                    Some(value) => format!("{value:.decimals$}"),
+                    None => null_text.to_string(),
+                });
+            }
+            rows.push(row);
+        }
+
+        let mut resampled = self.resampled(rows);
+        resampled.set_null_value(null_text.parse().ok());
+        resampled
+    }
+
+    fn resampled(&self, rows: Vec<Vec<String>>) -> AsciiLogData {
+        AsciiLogData::new(AsciiLogDataParams {
+            headers: self.headers.clone(),
+            rows,
+            comments: self.comments.clone(),
+            header: self.header.clone(),
+        })
+    }
+}
+
+impl LasFile {
+    /// Resamples ASCII log data onto a new "STEP", from "STRT" towards "STOP", and updates the Well section to match.
+    /// The sign of `step` doesn't matter, it follows the direction of the original index. See [`AsciiLogData::resample`].
+    pub fn resample(&mut self, step: f64, method: ResampleMethod) -> Result<(), ParseError> {
+        let (Some(start), Some(stop)) = (self.well_information.start(), self.well_information.stop()) else {
+            return Err(ParseError::Error {
+                message: "Can't resample without numeric \"STRT\" and \"STOP\" values!".to_string(),
+            });
+        };
+        if step == 0.0 || !step.is_finite() {
+            return Err(ParseError::InconsistentStep { start, stop, step });
+        }
+
+        let step = step.abs().copysign(stop - start);
+        let count = ((stop - start) / step + DEPTH_EPSILON).floor() as usize;
+        // Rounded to the precision of STRT and STEP, so float error doesn't end up in the index.
+        let scale = 10f64.powi(decimal_places(&step.to_string()).max(decimal_places(&start.to_string())) as i32);
+        let depths: Vec<f64> = (0..=count)
+            .map(|i| ((start + i as f64 * step) * scale).round() / scale)
+            .collect();
+        self.resample_to(&depths, method)
+    }
+
+    /// Resamples ASCII log data onto any depths, eg. the index of another well, and updates "STRT", "STOP"
+    /// and "STEP" to match. "STEP" is 0 when `depths` aren't evenly spaced. See [`AsciiLogData::resample`].
+    /// NULL values keep the text of the Well section "NULL", which is set to -999.25 when there is none.
+    pub fn resample_to(&mut self, depths: &[f64], method: ResampleMethod) -> Result<(), ParseError> {
+        if depths.is_empty() {
+            return Err(ParseError::Error {
+                message: "Can't resample onto no depths!".to_string(),
+            });
+        }
+
+        let null = &mut self.well_information.null.value;
+        let null_text = match null {
+            Some(value) => value.to_string(),
+            None => {
+                *null = LasValue::new(DEFAULT_NULL);
+                DEFAULT_NULL.to_string()
+            }
+        };
+        self.ascii_log_data = self.ascii_log_data.resample_with_null(depths, method, &null_text);
+        self.update_depth_range();
+        Ok(())
+    }
+
+    // Sets "STRT", "STOP" and "STEP" from the index curve. "STEP" is 0 when depths aren't evenly spaced.
+    pub(crate) fn update_depth_range(&mut self) {
+        let depths = self
+            .ascii_log_data
+            .index()
+            .map(|c| c.values().to_vec())
+            .unwrap_or_default();
+        let (Some(&first), Some(&last)) = (depths.first(), depths.last()) else {
+            return;
+        };
+        let steps: Vec<f64> = depths.windows(2).map(|w| w[1] - w[0]).collect();
+        let step = match steps.first() {
+            Some(&step)
+                if steps
+                    .iter()
+                    .all(|s| (s - step).abs() <= DEPTH_EPSILON * step.abs().max(1.0)) =>
+            {
+                step
+            }
+            _ => 0.0,
+        };
+
+        let index = self.ascii_log_data.index().map(Curve::raw).unwrap_or_default();
+        let decimals = index.iter().map(|r| decimal_places(r)).max().unwrap_or_default();
+        let well = &mut self.well_information;
+        well.strt.value = LasValue::new(&format!("{first:.decimals$}"));
+        well.stop.value = LasValue::new(&format!("{last:.decimals$}"));
+        well.step.value = LasValue::new(&format!("{step:.decimals$}"));
+    }
+}
+
+// Decimals of the most precise value in the curve.
+fn curve_decimals(curve: &Curve) -> usize {
+    curve.raw().iter().map(|r| decimal_places(r)).max().unwrap_or_default()
+}
+
+// Position of the first sample at or below `depth`, and of the first one above it.
+fn bracket(samples: &[(f64, usize)], depth: f64) -> Option<(usize, usize)> {
+    let (min, max) = (samples.first()?.0, samples.last()?.0);
+    if depth < min - DEPTH_EPSILON || depth > max + DEPTH_EPSILON {
+        return None;
+    }
+    let above = samples.partition_point(|&(d, _)| d <= depth + DEPTH_EPSILON);
+    Some((above.saturating_sub(1), above.min(samples.len() - 1)))
+}
+
+fn nearest(samples: &[(f64, usize)], curve: &Curve, depth: f64) -> Option<f64> {
+    let (below, above) = bracket(samples, depth)?;
+    let (d_below, row_below) = samples[below];
+    let (d_above, row_above) = samples[above];
+    let row = if (depth - d_below).abs() <= (d_above - depth).abs() {
+        row_below
+    } else {
+        row_above
+    };
+    curve.get(row)
+}
+
+fn linear(samples: &[(f64, usize)], curve: &Curve, depth: f64) -> Option<f64> {
+    let (below, above) = bracket(samples, depth)?;
+    let (d_below, row_below) = samples[below];
+    let (d_above, row_above) = samples[above];
+    if (depth - d_below).abs() <= DEPTH_EPSILON || below == above {
+        return curve.get(row_below);
+    }
+    let (v_below, v_above) = (curve.get(row_below)?, curve.get(row_above)?);
+    Some(v_below + (v_above - v_below) * (depth - d_below) / (d_above - d_below))
+}
+
+fn block_average(samples: &[(f64, usize)], curve: &Curve, depth: f64, (top, bottom): (f64, f64)) -> Option<f64> {
+    bracket(samples, depth)?;
+    let start = samples.partition_point(|&(d, _)| d < top - DEPTH_EPSILON);
+    let end = samples.partition_point(|&(d, _)| d < bottom - DEPTH_EPSILON);
+    let values: Vec<f64> = samples[start..end]
+        .iter()
+        .filter_map(|&(_, row)| curve.get(row))
+        .collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+// The block around `depths[i]` reaches halfway to the depths on either side, whichever way the
+// depths go. The first and last blocks are as big on the outside as they are on the inside.
+fn block_bounds(depths: &[f64], i: usize) -> (f64, f64) {
+    let depth = depths[i];
+    let neighbours = [i.checked_sub(1), Some(i + 1)].map(|j| j.and_then(|j| depths.get(j)).copied());
+    let half_way = |keep: fn(f64) -> bool| {
+        neighbours
+            .iter()
+            .flatten()
+            .map(|n| n - depth)
+            .filter(|&d| keep(d))
+            .map(|d| d.abs() / 2.0)
+            .reduce(f64::min)
+    };
+    let (above, below) = match (half_way(|d| d < 0.0), half_way(|d| d > 0.0)) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(a), None) => (a, a),
+        (None, Some(b)) => (b, b),
+        (None, None) => (0.0, 0.0),
+    };
+    (depth - above, depth + below)
+}
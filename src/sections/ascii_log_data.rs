@@ -1,6 +1,14 @@
-use crate::{ParseError, Section, SectionKind, write_comments};
-use serde::{Deserialize, Serialize};
-use std::fmt;
+use crate::{Delimiter, ParseError, Section, SectionEntry, SectionKind, write_comments};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, Visitor},
+    ser::{SerializeSeq, SerializeStruct},
+};
+use serde_json::value::RawValue;
+use std::{collections::HashMap, fmt};
+
+// Name of the newtype serde_json reads a `RawValue` through (`serde_json::raw::TOKEN`, which isn't public).
+const RAW_VALUE_TOKEN: &str = "$serde_json::private::RawValue";
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct AsciiLogDataParams {
@@ -10,52 +18,431 @@ pub struct AsciiLogDataParams {
     pub header: String,
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+// ================================================================================================
+// ------------------------ Curve -----------------------------------------------------------------
+// ================================================================================================
+
+/// A single column of ASCII log data.
+///
+/// Values are stored as numbers, along with the original text so a file can be written back
+/// out exactly as it was read. Cells that are not numeric (eg. LAS 3.0 string data) are `NaN`.
+#[derive(Debug, Default, Clone)]
+pub struct Curve {
+    pub mnemonic: String,
+    values: Vec<f64>,
+    raw: Vec<String>,
+    null: Option<f64>,
+}
+
+impl Curve {
+    pub fn new(mnemonic: &str) -> Self {
+        Self {
+            mnemonic: mnemonic.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Appends a value from its text representation, eg. "1670.000"
+    pub fn push(&mut self, raw: String) {
+        self.values.push(raw.parse().unwrap_or(f64::NAN));
+        self.raw.push(raw);
+    }
+
+    /// Appends a numeric value.
+    pub fn push_value(&mut self, value: f64) {
+        self.values.push(value);
+        self.raw.push(value.to_string());
+    }
+
+    /// Replaces the value at `index` from its text representation. Does nothing when out of bounds.
+    pub fn set(&mut self, index: usize, raw: String) {
+        if let (Some(value), Some(old_raw)) = (self.values.get_mut(index), self.raw.get_mut(index)) {
+            *value = raw.parse().unwrap_or(f64::NAN);
+            *old_raw = raw;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Every value, including the NULL sentinel. Use [`Curve::get`] or [`Curve::iter`] to have NULLs filtered out.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Original text of every value.
+    pub fn raw(&self) -> &[String] {
+        &self.raw
+    }
+
+    /// Value at `index`, or None when it is NULL, not numeric or out of bounds.
+    pub fn get(&self, index: usize) -> Option<f64> {
+        let value = *self.values.get(index)?;
+        (!self.is_null_value(value)).then_some(value)
+    }
+
+    /// Whether the value at `index` is NULL (or not numeric).
+    pub fn is_null(&self, index: usize) -> bool {
+        self.get(index).is_none()
+    }
+
+    /// Values with NULLs (and non numeric values) as None.
+    pub fn iter(&self) -> impl Iterator<Item = Option<f64>> + '_ {
+        self.values.iter().map(|&v| (!self.is_null_value(v)).then_some(v))
+    }
+
+    /// The NULL sentinel, from "NULL" in the Well section.
+    pub fn null_value(&self) -> Option<f64> {
+        self.null
+    }
+
+    fn is_null_value(&self, value: f64) -> bool {
+        value.is_nan() || self.null == Some(value)
+    }
+}
+
+// Values are derived from the raw text, comparing text also lets NaN (non numeric) cells be equal.
+impl PartialEq for Curve {
+    fn eq(&self, other: &Self) -> bool {
+        self.mnemonic == other.mnemonic && self.raw == other.raw
+    }
+}
+
+impl Eq for Curve {}
+
+// ================================================================================================
+// ------------------------ AsciiLogData ----------------------------------------------------------
+// ================================================================================================
+
+// Serialized as rows, in the same shape as AsciiLogDataParams.
+#[derive(Debug, Default, Deserialize)]
+#[serde(from = "AsciiLogDataRepr")]
 pub struct AsciiLogData {
     pub headers: Vec<String>,
-    pub rows: Vec<Vec<String>>,
+    pub curves: Vec<Curve>,
     pub comments: Option<Vec<String>>,
     pub header: String,
-    #[serde(skip)]
     pub(crate) line_number: usize,
+    null: Option<f64>,
 }
 
 impl AsciiLogData {
     pub fn new(params: AsciiLogDataParams) -> Self {
         Self::from(params)
     }
+
+    /// Curve by mnemonic (case insensitive).
+    pub fn curve(&self, mnemonic: &str) -> Option<&Curve> {
+        self.curves.iter().find(|c| c.mnemonic.eq_ignore_ascii_case(mnemonic))
+    }
+
+    /// The index curve, which is always the first curve ("DEPT", "DEPTH", "TIME" or "INDEX").
+    pub fn index(&self) -> Option<&Curve> {
+        self.curves.first()
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.curves.first().map(Curve::len).unwrap_or_default()
+    }
+
+    /// Original text of every value in a row. Curves shorter than the index curve (`curves` is pub) read as empty.
+    pub fn row(&self, index: usize) -> Option<Vec<&str>> {
+        if index >= self.num_rows() {
+            return None;
+        }
+        Some(self.curves.iter().map(|c| raw_at(c, index)).collect())
+    }
+
+    /// Original text of every value, row by row. See [`AsciiLogData::row`].
+    pub fn rows(&self) -> impl Iterator<Item = Vec<&str>> + '_ {
+        (0..self.num_rows()).map(|i| self.curves.iter().map(|c| raw_at(c, i)).collect())
+    }
+
+    /// Appends a row of values in their text representation. Curves are created
+    /// from `headers` as needed. Missing values are left empty, same as missing cells of columnar JSON,
+    /// so every curve stays the same length. Empty values are written as "NULL" when read from JSON or YAML.
+    pub fn push_row(&mut self, mut row: Vec<String>) {
+        let num_rows = self.num_rows();
+        while self.curves.len() < row.len() {
+            let mnemonic = self
+                .headers
+                .get(self.curves.len())
+                .map(String::as_str)
+                .unwrap_or_default();
+            let mut curve = Curve::new(mnemonic);
+            curve.null = self.null;
+            for _ in 0..num_rows {
+                curve.push(String::new());
+            }
+            self.curves.push(curve);
+        }
+        row.resize(self.curves.len(), String::new());
+        for (curve, value) in self.curves.iter_mut().zip(row) {
+            curve.push(value);
+        }
+    }
+
+    /// Sets the NULL sentinel used by every curve (see [`Curve::get`]).
+    /// This is done for you when parsing, using "NULL" from the Well section.
+    pub fn set_null_value(&mut self, null: Option<f64>) {
+        self.null = null;
+        for curve in self.curves.iter_mut() {
+            curve.null = null;
+        }
+    }
+
+    // Writes the raw values of a row, separated by `delimiter`.
+    fn write_row(&self, f: &mut fmt::Formatter<'_>, index: usize, delimiter: Delimiter) -> fmt::Result {
+        for (i, curve) in self.curves.iter().enumerate() {
+            if i != 0 {
+                write!(f, "{}", delimiter.as_str())?;
+            }
+            write!(f, "{}", delimiter.quote(raw_at(curve, index)))?;
+        }
+        writeln!(f)
+    }
+
+    // Writes rows separated by a LAS 3.0 "DLM" delimiter.
+    pub(crate) fn write_delimited(&self, f: &mut fmt::Formatter<'_>, delimiter: Delimiter) -> fmt::Result {
+        if delimiter == Delimiter::Space {
+            return write!(f, "{self}");
+        }
+        write_comments(f, &self.comments)?;
+        writeln!(f, "{}", self.header)?;
+        for i in 0..self.num_rows() {
+            self.write_row(f, i, delimiter)?;
+        }
+        Ok(())
+    }
+
+    // Writes rows in wrapped ("WRAP. YES") form, see `write_wrapped_row`.
+    pub(crate) fn write_wrapped(&self, f: &mut fmt::Formatter<'_>, line_width: usize) -> fmt::Result {
+        write_comments(f, &self.comments)?;
+        writeln!(f, "{}", self.header)?;
+        for row in self.rows() {
+            write_wrapped_row(f, &row, line_width)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes one depth step in wrapped ("WRAP. YES") form. Per CWLS v2.0 the index (first) value goes on its
+// own line, remaining values are packed onto lines no longer than `line_width`.
+pub(crate) fn write_wrapped_row<S>(out: &mut impl fmt::Write, row: &[S], line_width: usize) -> fmt::Result
+where
+    S: AsRef<str>,
+{
+    let Some((index, values)) = row.split_first() else {
+        return Ok(());
+    };
+    writeln!(out, "{}", index.as_ref())?;
+    let mut line_len = 0;
+    for value in values.iter().map(AsRef::as_ref) {
+        if line_len > 0 && line_len + 1 + value.len() > line_width {
+            writeln!(out)?;
+            line_len = 0;
+        }
+        if line_len > 0 {
+            write!(out, " ")?;
+            line_len += 1;
+        }
+        write!(out, "{value}")?;
+        line_len += value.len();
+    }
+    if line_len > 0 {
+        writeln!(out)?;
+    }
+    Ok(())
 }
 
 impl From<AsciiLogDataParams> for AsciiLogData {
     fn from(params: AsciiLogDataParams) -> Self {
-        Self {
+        let mut ascii_logs = Self {
             headers: params.headers,
-            rows: params.rows,
+            curves: vec![],
             comments: params.comments,
             header: params.header,
             line_number: 0,
+            null: None,
+        };
+        for row in params.rows {
+            ascii_logs.push_row(row);
+        }
+        ascii_logs
+    }
+}
+
+// "AsciiLogData" as it may be written in JSON or YAML : rows, or one array per curve (see
+// `JsonOptions::columnar`), of strings, numbers or null (NULL).
+#[derive(Deserialize)]
+struct AsciiLogDataRepr {
+    headers: Vec<String>,
+    #[serde(default)]
+    rows: Vec<Vec<Cell>>,
+    #[serde(default)]
+    columns: Option<HashMap<String, Vec<Cell>>>,
+    comments: Option<Vec<String>>,
+    header: String,
+}
+
+impl From<AsciiLogDataRepr> for AsciiLogData {
+    fn from(repr: AsciiLogDataRepr) -> Self {
+        let mut rows: Vec<Vec<String>> = repr
+            .rows
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.0).collect())
+            .collect();
+        if let Some(columns) = repr.columns {
+            rows = columns_to_rows(&repr.headers, columns);
+        }
+        Self::from(AsciiLogDataParams {
+            headers: repr.headers,
+            rows,
+            comments: repr.comments,
+            header: repr.header,
+        })
+    }
+}
+
+// Text of a value, empty when the curve is shorter than the others.
+fn raw_at(curve: &Curve, index: usize) -> &str {
+    curve.raw.get(index).map_or("", String::as_str)
+}
+
+// Rows from one array of values per curve, see `JsonOptions::columnar`.
+pub(crate) fn columns_to_rows(headers: &[String], columns: HashMap<String, Vec<Cell>>) -> Vec<Vec<String>> {
+    let columns: Vec<&[Cell]> = headers
+        .iter()
+        .map(|h| columns.get(h).map(Vec::as_slice).unwrap_or_default())
+        .collect();
+    let num_rows = columns.iter().map(|c| c.len()).max().unwrap_or_default();
+    (0..num_rows)
+        .map(|i| {
+            columns
+                .iter()
+                .map(|c| c.get(i).map(|cell| cell.0.clone()).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+// A single value of ASCII log data from JSON or YAML. Numbers are kept as text, null comes through
+// empty and is filled in with "NULL" once the Well section is known.
+pub(crate) struct Cell(pub(crate) String);
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CellVisitor;
+
+        impl<'de> Visitor<'de> for CellVisitor {
+            type Value = Cell;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a string, a number or null")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Cell, E> {
+                Ok(Cell(v.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Cell, E> {
+                Ok(Cell(v.to_string()))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Cell, E> {
+                Ok(Cell(v.to_string()))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Cell, E> {
+                Ok(Cell(v.to_string()))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Cell, E> {
+                Ok(Cell(String::new()))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Cell, E> {
+                Ok(Cell(String::new()))
+            }
+
+            // Anything but serde_json.
+            fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Cell, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+
+            // serde_json hands over the JSON text of the value, so numbers keep their original text ("1670.000"
+            // isn't "1670").
+            fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Cell, A::Error> {
+                let raw = Box::<RawValue>::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                match raw.get() {
+                    "null" => Ok(Cell(String::new())),
+                    text if text.starts_with('"') => serde_json::from_str(text).map(Cell).map_err(de::Error::custom),
+                    text => Ok(Cell(text.to_string())),
+                }
+            }
         }
+
+        // The name serde_json looks for to read a `RawValue`, other deserializers visit the value as is.
+        deserializer.deserialize_newtype_struct(RAW_VALUE_TOKEN, CellVisitor)
     }
 }
 
 impl PartialEq for AsciiLogData {
     fn eq(&self, other: &Self) -> bool {
         self.headers == other.headers
-            && self.rows == other.rows
+            && self.curves == other.curves
             && self.comments == other.comments
             && self.header == other.header
     }
 }
 
+impl Serialize for AsciiLogData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("AsciiLogData", 4)?;
+        state.serialize_field("headers", &self.headers)?;
+        state.serialize_field("comments", &self.comments)?;
+        state.serialize_field("header", &self.header)?;
+        state.serialize_field("rows", &SerializeRows(self))?;
+        state.end()
+    }
+}
+
+// Serializes columnar curves as rows, without collecting them into rows first.
+struct SerializeRows<'a>(&'a AsciiLogData);
+
+impl Serialize for SerializeRows<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.0.num_rows()))?;
+        for row in self.0.rows() {
+            seq.serialize_element(&row)?;
+        }
+        seq.end()
+    }
+}
+
 impl Eq for AsciiLogData {}
 
 impl fmt::Display for AsciiLogData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write_comments(f, &self.comments)?;
         writeln!(f, "{}", self.header)?;
-        for row in self.rows.iter() {
+        for row in self.rows() {
             for cell in row.iter() {
-                write!(f, "{} ", cell.clone())?;
+                write!(f, "{} ", Delimiter::Space.quote(cell))?;
             }
             writeln!(f)?;
         }
@@ -67,7 +454,7 @@ impl TryFrom<Section> for AsciiLogData {
     type Error = ParseError;
 
     fn try_from(section: Section) -> Result<Self, Self::Error> {
-        if section.header.kind != SectionKind::AsciiLogData {
+        if !matches!(section.header.kind, SectionKind::AsciiLogData | SectionKind::GroupData) {
             return Err(ParseError::UnexpectedSection {
                 expected: SectionKind::AsciiLogData,
                 got: section.header.kind,
@@ -83,8 +470,19 @@ impl TryFrom<Section> for AsciiLogData {
         let mut ascii_logs = AsciiLogData::default();
 
         if let Some(headers) = section.ascii_headers {
+            ascii_logs.curves = headers.iter().map(|h| Curve::new(h)).collect();
             ascii_logs.headers = headers;
-            ascii_logs.rows = section.ascii_rows;
+        }
+
+        for row in section.ascii_rows {
+            ascii_logs.push_row(row);
+        }
+
+        // Rows in LAS 3.0 section groups (other than "Log") are handed to us as entries.
+        for entry in section.entries {
+            if let SectionEntry::AsciiLogData(row) = entry {
+                ascii_logs.push_row(row);
+            }
         }
 
         ascii_logs.header = format!("~{}", section.header.raw);
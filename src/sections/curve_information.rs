@@ -58,7 +58,7 @@ impl TryFrom<Section> for CurveInformation {
     type Error = ParseError;
 
     fn try_from(section: Section) -> Result<Self, Self::Error> {
-        if section.header.kind != SectionKind::Curve {
+        if !matches!(section.header.kind, SectionKind::Curve | SectionKind::GroupDefinition) {
             return Err(ParseError::UnexpectedSection {
                 expected: SectionKind::Curve,
                 got: section.header.kind,
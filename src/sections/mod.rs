@@ -2,6 +2,7 @@ mod ascii_log_data;
 mod curve_information;
 mod other_information;
 mod parameter_information;
+mod section_group;
 mod version_information;
 mod well_information;
 
@@ -9,5 +10,6 @@ pub use ascii_log_data::*;
 pub use curve_information::*;
 pub use other_information::*;
 pub use parameter_information::*;
+pub use section_group::*;
 pub use version_information::*;
 pub use well_information::*;
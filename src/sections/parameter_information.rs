@@ -58,7 +58,10 @@ impl TryFrom<Section> for ParameterInformation {
     type Error = ParseError;
 
     fn try_from(section: Section) -> Result<Self, Self::Error> {
-        if section.header.kind != SectionKind::Parameter {
+        if !matches!(
+            section.header.kind,
+            SectionKind::Parameter | SectionKind::GroupParameter
+        ) {
             return Err(ParseError::UnexpectedSection {
                 expected: SectionKind::Parameter,
                 got: section.header.kind,
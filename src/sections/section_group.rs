@@ -0,0 +1,79 @@
+use crate::{
+    Delimiter, ParseError, Section, SectionKind,
+    sections::{AsciiLogData, Curve, CurveInformation, ParameterInformation},
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A LAS 3.0 section group, made up of "~{name}_Parameter", "~{name}_Definition" and
+/// one or more "~{name}_Data" sections. eg. "~Core_Parameter", "~Core_Definition", "~Core_Data".
+///
+/// The "Log" group is not represented here, it maps onto the LAS 2.0 sections
+/// (ParameterInformation, CurveInformation and AsciiLogData).
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SectionGroup {
+    pub name: String,
+    pub parameter: Option<ParameterInformation>,
+    pub definition: Option<CurveInformation>,
+    pub data: Vec<AsciiLogData>,
+}
+
+impl SectionGroup {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Column of the first data section for the given definition mnemonic, eg. "TOPN" in "~Tops_Data".
+    pub fn column(&self, mnemonic: &str) -> Option<&Curve> {
+        self.data.first()?.curve(mnemonic)
+    }
+
+    // Adds a group section to the group it belongs to, creating that group if we haven't seen it yet.
+    pub(crate) fn add_section(groups: &mut Vec<SectionGroup>, section: Section) -> Result<(), ParseError> {
+        let name = section.header.group.clone().unwrap_or_default();
+
+        let group = match groups.iter().position(|g| g.name.eq_ignore_ascii_case(&name)) {
+            Some(i) => &mut groups[i],
+            None => {
+                groups.push(SectionGroup::new(&name));
+                groups.last_mut().expect("just pushed")
+            }
+        };
+
+        match section.header.kind {
+            SectionKind::GroupParameter => group.parameter = Some(ParameterInformation::try_from(section)?),
+            SectionKind::GroupDefinition => group.definition = Some(CurveInformation::try_from(section)?),
+            SectionKind::GroupData => group.data.push(AsciiLogData::try_from(section)?),
+            kind => {
+                return Err(ParseError::UnexpectedSection {
+                    expected: SectionKind::GroupData,
+                    got: kind,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn write_las(&self, f: &mut fmt::Formatter<'_>, delimiter: Delimiter) -> fmt::Result {
+        if let Some(parameter) = self.parameter.as_ref() {
+            write!(f, "{parameter}")?;
+        }
+        if let Some(definition) = self.definition.as_ref() {
+            write!(f, "{definition}")?;
+        }
+        for data in self.data.iter() {
+            data.write_delimited(f, delimiter)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SectionGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_las(f, Delimiter::Space)
+    }
+}
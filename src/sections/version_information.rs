@@ -1,4 +1,4 @@
-use crate::{DataLine, ParseError, Section, SectionEntry, SectionKind, write_comments};
+use crate::{DataLine, Delimiter, LasValue, ParseError, Section, SectionEntry, SectionKind, write_comments};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -28,6 +28,63 @@ impl VersionInformation {
     pub fn new(params: VersionInformationParams) -> Self {
         Self::from(params)
     }
+
+    /// Whether "WRAP" is "YES", meaning a depth step in the ASCII log data may span multiple lines.
+    pub fn is_wrapped(&self) -> bool {
+        self.wrap
+            .value
+            .as_ref()
+            .is_some_and(|v| v.to_string().eq_ignore_ascii_case("YES"))
+    }
+
+    /// Whether "VERS" is 3.x
+    pub fn is_las3(&self) -> bool {
+        self.version
+            .value
+            .as_ref()
+            .is_some_and(|v| v.to_string().starts_with('3'))
+    }
+
+    /// LAS 3.0 "DLM" line, which separates values in data sections. Defaults to SPACE.
+    pub fn delimiter(&self) -> Delimiter {
+        self.additional
+            .iter()
+            .find(|kv| kv.mnemonic.eq_ignore_ascii_case("DLM"))
+            .and_then(|kv| kv.value.as_ref())
+            .and_then(|v| Delimiter::parse(&v.to_string()))
+            .unwrap_or_default()
+    }
+
+    /// Every data line in the order they are written.
+    pub fn data_lines(&self) -> Vec<&DataLine> {
+        let mut lines = vec![&self.version, &self.wrap];
+        lines.extend(self.additional.iter());
+        lines
+    }
+
+    // Writes this section, forcing the "WRAP" line to agree with `wrapped`.
+    pub(crate) fn write_las(&self, f: &mut fmt::Formatter<'_>, wrapped: bool) -> fmt::Result {
+        write_comments(f, &self.comments)?;
+        writeln!(f, "{}", self.header)?;
+        writeln!(f, "{}", self.version)?;
+        if wrapped == self.is_wrapped() {
+            writeln!(f, "{}", self.wrap)?;
+        } else {
+            let (value, description) = if wrapped {
+                ("YES", "Multiple lines per depth step")
+            } else {
+                ("NO", "One line per depth step")
+            };
+            let mut wrap = self.wrap.clone();
+            wrap.value = LasValue::new(value);
+            wrap.description = Some(description.to_string());
+            writeln!(f, "{wrap}")?;
+        }
+        for addition in self.additional.iter() {
+            writeln!(f, "{addition}")?;
+        }
+        Ok(())
+    }
 }
 
 impl From<VersionInformationParams> for VersionInformation {
@@ -57,21 +114,23 @@ impl Eq for VersionInformation {}
 
 impl fmt::Display for VersionInformation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write_comments(f, &self.comments)?;
-        writeln!(f, "{}", self.header)?;
-        writeln!(f, "{}", self.version)?;
-        writeln!(f, "{}", self.wrap)?;
-        for addition in self.additional.iter() {
-            writeln!(f, "{addition}")?;
-        }
-        Ok(())
+        self.write_las(f, self.is_wrapped())
     }
 }
 
-impl TryFrom<Section> for VersionInformation {
-    type Error = ParseError;
+impl VersionInformation {
+    pub fn validate(&self) -> Result<(), ParseError> {
+        if self.version.mnemonic.is_empty() || self.wrap.mnemonic.is_empty() {
+            return Err(ParseError::SectionMissingRequiredData {
+                section: SectionKind::Version,
+                one_of: vec!["VERS".to_string(), "WRAP".to_string()],
+            });
+        }
+        Ok(())
+    }
 
-    fn try_from(section: Section) -> Result<Self, Self::Error> {
+    // Builds the section without validating it, so lenient parsing can report problems and carry on.
+    pub(crate) fn from_section(section: Section) -> Result<Self, ParseError> {
         if section.header.kind != SectionKind::Version {
             return Err(ParseError::UnexpectedSection {
                 expected: SectionKind::Version,
@@ -80,35 +139,30 @@ impl TryFrom<Section> for VersionInformation {
         }
 
         let mut version = VersionInformation::default();
-        let mut has_vers = false;
-        let mut has_wrap = false;
 
         for entry in section.entries {
             if let SectionEntry::Delimited(kv) = entry {
                 match kv.mnemonic.to_lowercase().as_str() {
-                    "vers" => {
-                        version.version = kv;
-                        has_vers = true;
-                    }
-                    "wrap" => {
-                        version.wrap = kv;
-                        has_wrap = true;
-                    }
+                    "vers" => version.version = kv,
+                    "wrap" => version.wrap = kv,
                     _ => version.additional.push(kv),
                 };
             }
         }
 
-        if !has_vers || !has_wrap {
-            return Err(ParseError::SectionMissingRequiredData {
-                section: SectionKind::Version,
-                one_of: vec!["VERS".to_string(), "WRAP".to_string()],
-            });
-        }
-
         version.header = format!("~{}", section.header.raw);
         version.comments = section.comments;
         version.line_number = section.line;
         Ok(version)
     }
 }
+
+impl TryFrom<Section> for VersionInformation {
+    type Error = ParseError;
+
+    fn try_from(section: Section) -> Result<Self, Self::Error> {
+        let version = VersionInformation::from_section(section)?;
+        version.validate()?;
+        Ok(version)
+    }
+}
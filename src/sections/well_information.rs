@@ -1,6 +1,8 @@
 use crate::{
-    DataLine, LasValue, ParseError, Section, SectionEntry, SectionKind, any_present, write_comments, write_kv_opt,
+    DataLine, LasValue, ParseError, Section, SectionEntry, SectionKind, any_present, parse::DepthCheck,
+    sections::AsciiLogData, write_comments,
 };
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -81,8 +83,79 @@ impl WellInformation {
     pub fn new(params: WellInformationParams) -> Self {
         Self::from(params)
     }
+
+    /// Every data line in the order they are written.
+    pub fn data_lines(&self) -> Vec<&DataLine> {
+        // Mandatory fields
+        let mut lines = vec![&self.strt, &self.stop, &self.step, &self.null];
+        // Optional standard fields
+        lines.extend([&self.comp, &self.well, &self.fld, &self.loc].into_iter().flatten());
+        // Location (one-of, but spec allows multiple lines syntactically)
+        let location = [&self.prov, &self.cnty, &self.stat, &self.ctry, &self.srvc, &self.date];
+        lines.extend(location.into_iter().flatten());
+        // Identity (one-of)
+        lines.extend([&self.uwi, &self.api].into_iter().flatten());
+        // Additional user-defined lines
+        lines.extend(self.additional.iter());
+        lines
+    }
+
+    pub(crate) fn data_lines_mut(&mut self) -> Vec<&mut DataLine> {
+        let mut lines = vec![&mut self.strt, &mut self.stop, &mut self.step, &mut self.null];
+        let optional = [
+            &mut self.comp,
+            &mut self.well,
+            &mut self.fld,
+            &mut self.loc,
+            &mut self.prov,
+            &mut self.cnty,
+            &mut self.stat,
+            &mut self.ctry,
+            &mut self.srvc,
+            &mut self.date,
+            &mut self.uwi,
+            &mut self.api,
+        ];
+        lines.extend(optional.into_iter().flatten());
+        lines.extend(self.additional.iter_mut());
+        lines
+    }
+
+    /// "STRT" as a number, eg. 1670.0
+    pub fn start(&self) -> Option<f64> {
+        self.strt.value.as_ref()?.as_f64()
+    }
+
+    /// "STOP" as a number, eg. 1669.75
+    pub fn stop(&self) -> Option<f64> {
+        self.stop.value.as_ref()?.as_f64()
+    }
+
+    /// "STEP" as a number, eg. -0.125. Zero means the spacing is not constant.
+    pub fn step(&self) -> Option<f64> {
+        self.step.value.as_ref()?.as_f64()
+    }
+
+    /// "NULL" as a number, eg. -999.25
+    pub fn null_value(&self) -> Option<f64> {
+        self.null.value.as_ref()?.as_f64()
+    }
+
+    /// "DATE" as a calendar date. The spec doesn't say how dates are written, so we try the
+    /// common ones, eg. "13-DEC-86", "23 DEC 1986" or "1986-12-13".
+    /// Dates written with numbers only are read day first ("13/12/1986"), except ISO 8601.
+    pub fn date(&self) -> Option<NaiveDate> {
+        let raw = self.date.as_ref()?.value.as_ref()?.to_string();
+        DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(&raw, format).ok())
+    }
 }
 
+const DATE_FORMATS: [&str; 8] = [
+    "%Y-%m-%d", "%d-%b-%y", "%d-%b-%Y", "%d %b %y", "%d %b %Y", "%d/%m/%Y", "%d-%m-%Y", "%d.%m.%Y",
+];
+
 impl From<WellInformationParams> for WellInformation {
     fn from(params: WellInformationParams) -> Self {
         Self {
@@ -140,59 +213,54 @@ impl fmt::Display for WellInformation {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write_comments(f, &self.comments)?;
         writeln!(f, "{}", self.header)?;
-        // Mandatory fields
-        writeln!(f, "{}", self.strt)?;
-        writeln!(f, "{}", self.stop)?;
-        writeln!(f, "{}", self.step)?;
-        writeln!(f, "{}", self.null)?;
-        // Optional standard fields
-        write_kv_opt(f, &self.comp)?;
-        write_kv_opt(f, &self.well)?;
-        write_kv_opt(f, &self.fld)?;
-        write_kv_opt(f, &self.loc)?;
-        // Location (one-of, but spec allows multiple lines syntactically)
-        write_kv_opt(f, &self.prov)?;
-        write_kv_opt(f, &self.cnty)?;
-        write_kv_opt(f, &self.stat)?;
-        write_kv_opt(f, &self.ctry)?;
-        write_kv_opt(f, &self.srvc)?;
-        write_kv_opt(f, &self.date)?;
-        // Identity (one-of)
-        write_kv_opt(f, &self.uwi)?;
-        write_kv_opt(f, &self.api)?;
-        // Additional user-defined lines
-        for kv in &self.additional {
+        for kv in self.data_lines() {
             writeln!(f, "{kv}")?;
         }
         Ok(())
     }
 }
 
-// TODO : maybe move this validation into the parser?
 impl WellInformation {
     pub fn validate(&self) -> Result<(), ParseError> {
+        match self.validation_errors().into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Every rule the section breaks, in the order [`WellInformation::validate`] checks them.
+    pub fn validation_errors(&self) -> Vec<ParseError> {
+        let mut errors = vec![];
+
         // These data lines are required.
-        self.require_value(&self.strt, "STRT")?;
-        self.require_value(&self.stop, "STOP")?;
-        self.require_value(&self.step, "STEP")?;
-        self.require_value(&self.null, "NULL")?;
-
-        // These must be numeric values.
-        self.require_numeric(&self.strt, "STRT")?;
-        self.require_numeric(&self.stop, "STOP")?;
-        self.require_numeric(&self.step, "STEP")?;
-
-        // TODO : Step consistency
-        //
-        //if let Some(LasValue::Float(step)) = &self.step.value
-        //    && *step == 0.0
-        //{
-        // allowed but special case
-        //}
+        let required = [
+            (&self.strt, "STRT"),
+            (&self.stop, "STOP"),
+            (&self.step, "STEP"),
+            (&self.null, "NULL"),
+        ];
+        for (kv, name) in required {
+            errors.extend(self.require_value(kv, name).err());
+        }
+
+        // These must be numeric values (missing ones were reported above).
+        for (kv, name) in [(&self.strt, "STRT"), (&self.stop, "STOP"), (&self.step, "STEP")] {
+            if kv.value.is_some() {
+                errors.extend(self.require_numeric(kv, name).err());
+            }
+        }
+
+        // "STEP" has to head from "STRT" towards "STOP". Zero means the spacing isn't constant, which is allowed.
+        if let (Some(start), Some(stop), Some(step)) = (self.start(), self.stop(), self.step())
+            && step != 0.0
+            && (stop - start) * step < 0.0
+        {
+            errors.push(ParseError::InconsistentStep { start, stop, step });
+        }
 
         // "Location" must contain one of "PROV", "CNTY", "STAT" or "CTRY".
         if !any_present(&[&self.prov, &self.cnty, &self.stat, &self.ctry]) {
-            return Err(ParseError::SectionMissingRequiredData {
+            errors.push(ParseError::SectionMissingRequiredData {
                 section: SectionKind::Well,
                 one_of: vec![
                     "PROV".to_string(),
@@ -205,13 +273,43 @@ impl WellInformation {
 
         // "Identity" must contain one of "UWI" or "API".
         if !any_present(&[&self.uwi, &self.api]) {
-            return Err(ParseError::SectionMissingRequiredData {
+            errors.push(ParseError::SectionMissingRequiredData {
                 section: SectionKind::Well,
                 one_of: vec!["UWI".to_string(), "API".to_string()],
             });
         }
 
-        Ok(())
+        errors
+    }
+
+    /// Checks that `data` agrees with this section : the first and last depths are "STRT" and "STOP",
+    /// depths are "STEP" apart and only move one way, and a "STEP" of 0 really means irregular sampling.
+    /// See [`crate::ParseOptions::validate_data`] to check while parsing, with line numbers.
+    pub fn validate_data(&self, data: &AsciiLogData) -> Result<(), ParseError> {
+        match self.data_validation_errors(data).into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    /// Every way `data` disagrees with this section, row by row. See [`WellInformation::validate_data`].
+    pub fn data_validation_errors(&self, data: &AsciiLogData) -> Vec<ParseError> {
+        let mut check = DepthCheck::new(
+            self.strt.value.as_ref(),
+            self.stop.value.as_ref(),
+            self.step.value.as_ref(),
+        );
+        let mut errors: Vec<ParseError> = data
+            .index()
+            .map(|index| index.raw())
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|depth| check.row(depth))
+            .collect();
+        let (stop, undeclared_step) = check.finish();
+        errors.extend(stop);
+        errors.extend(undeclared_step);
+        errors
     }
 
     fn require_value(&self, kv: &DataLine, name: &str) -> Result<(), ParseError> {
@@ -226,14 +324,7 @@ impl WellInformation {
 
     fn require_numeric(&self, kv: &DataLine, name: &str) -> Result<(), ParseError> {
         match &kv.value {
-            Some(LasValue::Int(_)) => Ok(()),
-            Some(LasValue::Text(t)) => {
-                t.parse::<f64>().map_err(|_| ParseError::InvalidWellValue {
-                    mnemonic: name.to_string(),
-                    value: kv.value.clone(),
-                })?;
-                Ok(())
-            }
+            Some(LasValue::Int(_) | LasValue::Float(_)) => Ok(()),
             _ => Err(ParseError::InvalidWellValue {
                 mnemonic: name.to_string(),
                 value: kv.value.clone(),
@@ -246,6 +337,15 @@ impl TryFrom<Section> for WellInformation {
     type Error = ParseError;
 
     fn try_from(section: Section) -> Result<Self, Self::Error> {
+        let well = WellInformation::from_section(section)?;
+        well.validate()?;
+        Ok(well)
+    }
+}
+
+impl WellInformation {
+    // Builds the section without validating it, so lenient parsing can report problems and carry on.
+    pub(crate) fn from_section(section: Section) -> Result<Self, ParseError> {
         if section.header.kind != SectionKind::Well {
             return Err(ParseError::UnexpectedSection {
                 expected: SectionKind::Well,
@@ -287,8 +387,6 @@ impl TryFrom<Section> for WellInformation {
         well.header = format!("~{}", section.header.raw);
         well.comments = section.comments;
         well.line_number = section.line;
-
-        well.validate()?;
         Ok(well)
     }
 }
@@ -0,0 +1,80 @@
+use crate::{
+    FilterOptions, LasFile, LasValue, ParseError,
+    parse::missing_curve,
+    sections::{AsciiLogData, AsciiLogDataParams},
+};
+
+impl LasFile {
+    /// Keeps only the rows of ASCII log data from `top` to `base` (inclusive, either way round), eg. one zone,
+    /// and sets "STRT" and "STOP" to the first and last depth left. See [`crate::FilterSink`] to crop while streaming.
+    pub fn slice_depth(&mut self, top: f64, base: f64) -> Result<(), ParseError> {
+        let options = FilterOptions {
+            top: Some(top),
+            base: Some(base),
+            curves: None,
+        };
+        let data = &self.ascii_log_data;
+        let rows: Vec<Vec<String>> = data
+            .index()
+            .map(|index| index.iter().enumerate())
+            .into_iter()
+            .flatten()
+            .filter(|(_, depth)| depth.is_some_and(|d| options.keeps_depth(d)))
+            .filter_map(|(i, _)| data.row(i))
+            .map(|row| row.into_iter().map(str::to_string).collect())
+            .collect();
+        let (Some(first), Some(last)) = (rows.first(), rows.last()) else {
+            return Err(ParseError::Error {
+                message: format!("There is no ASCII log data between {top} and {base}!"),
+            });
+        };
+        let (start, stop) = (LasValue::new(&first[0]), LasValue::new(&last[0]));
+
+        self.ascii_log_data = AsciiLogData::new(AsciiLogDataParams {
+            headers: std::mem::take(&mut self.ascii_log_data.headers),
+            rows,
+            comments: self.ascii_log_data.comments.take(),
+            header: std::mem::take(&mut self.ascii_log_data.header),
+        });
+        self.apply_null_value();
+        self.well_information.strt.value = start;
+        self.well_information.stop.value = stop;
+        Ok(())
+    }
+
+    /// Keeps only the curves in `mnemonics` (case insensitive), in the order they are in the file, eg.
+    /// `select_curves(&["GR", "RHOB", "NPHI"])`. The index curve is always kept.
+    /// See [`crate::FilterSink`] to drop curves while streaming.
+    pub fn select_curves(&mut self, mnemonics: &[&str]) -> Result<(), ParseError> {
+        let curves = &mut self.curve_information.curves;
+        if let Some(missing) = mnemonics
+            .iter()
+            .find(|m| !curves.iter().any(|c| c.mnemonic.eq_ignore_ascii_case(m)))
+        {
+            return Err(missing_curve(missing));
+        }
+
+        let options = FilterOptions {
+            curves: Some(mnemonics.iter().map(|m| m.to_string()).collect()),
+            ..Default::default()
+        };
+        let keep: Vec<bool> = curves
+            .iter()
+            .enumerate()
+            .map(|(i, c)| i == 0 || options.keeps_curve(&c.mnemonic))
+            .collect();
+        retain_positions(curves, &keep);
+        retain_positions(&mut self.ascii_log_data.curves, &keep);
+        retain_positions(&mut self.ascii_log_data.headers, &keep);
+        Ok(())
+    }
+}
+
+// Keeps the items whose position is true in `keep`.
+fn retain_positions<T>(items: &mut Vec<T>, keep: &[bool]) {
+    let mut position = 0;
+    items.retain(|_| {
+        position += 1;
+        keep.get(position - 1).copied().unwrap_or(true)
+    });
+}
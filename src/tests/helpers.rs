@@ -4,6 +4,7 @@ use super::*;
 use std::{
     io::{Cursor, Seek},
     ops::Range,
+    path::{Path, PathBuf},
 };
 
 pub(crate) fn generate_temp_las(size_in_mb: usize) -> std::io::Result<Cursor<Vec<u8>>> {
@@ -81,16 +82,73 @@ TDEPTH.FT               0.0000: Total Depth
     Ok(writer)
 }
 
+// Every line of `las_files/_good_sample_1.las` up to and including "~A", to put generated rows after.
+pub(crate) fn sample_header() -> String {
+    let sample = std::fs::read_to_string("las_files/_good_sample_1.las").unwrap();
+    // "~A" also appears in a comment, so look for it at the start of a line.
+    let header_end = sample.find("\n~A").unwrap() + 1;
+    let header_end = header_end + sample[header_end..].find('\n').unwrap() + 1;
+    sample[..header_end].to_string()
+}
+
+// A directory of our own under the system temp dir, removed when dropped so a failing test cleans up too.
+// The process id keeps test runs that happen at the same time apart.
+pub(crate) struct TestDir(PathBuf);
+
+impl TestDir {
+    pub(crate) fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("liblas_test_{name}_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.0
+    }
+
+    pub(crate) fn join(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.0.join(path)
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
 pub(crate) fn depths(las: &LasFile) -> Vec<f64> {
     let first_header_col = &las.ascii_log_data.headers[0];
     if !first_header_col.to_lowercase().starts_with("dept") {
         panic!("first header column is not depth! got {first_header_col}");
     }
-    las.ascii_log_data
-        .rows
-        .iter()
-        .map(|row| row[0].parse::<f64>().unwrap())
-        .collect()
+    las.index().expect("index curve").values().to_vec()
+}
+
+// JSON and YAML write numbers as numbers, so "1670.000" comes back as "1670". Compares ASCII log data
+// by value (NULL and text included), and everything else exactly.
+pub(crate) fn assert_same_values(expected: &LasFile, found: &LasFile) {
+    let (a, b) = (&expected.ascii_log_data, &found.ascii_log_data);
+    assert_eq!(a.headers, b.headers);
+    assert_eq!(a.comments, b.comments);
+    assert_eq!(a.header, b.header);
+    assert_eq!(a.curves.len(), b.curves.len());
+    for (curve, other) in a.curves.iter().zip(b.curves.iter()) {
+        assert_eq!(curve.len(), other.len(), "{}", curve.mnemonic);
+        for i in 0..curve.len() {
+            match (curve.values()[i], other.values()[i]) {
+                (x, y) if x.is_nan() && y.is_nan() => assert_eq!(curve.raw()[i], other.raw()[i]),
+                (x, y) => assert_eq!(x, y, "{} row {i}", curve.mnemonic),
+            }
+        }
+    }
+    assert_eq!(expected.version_information, found.version_information);
+    assert_eq!(expected.well_information, found.well_information);
+    assert_eq!(expected.curve_information, found.curve_information);
+    assert_eq!(expected.other_information, found.other_information);
+    assert_eq!(expected.parameter_information, found.parameter_information);
+    assert_eq!(expected.section_groups, found.section_groups);
 }
 
 /// One plotted curve (column)
@@ -137,21 +195,11 @@ pub(crate) fn plot_curves(las: &LasFile) -> Vec<PlotCurve> {
 
 /// Compute per-curve X axis range ignoring NULLs
 pub(crate) fn x_range_for_curve(las: &LasFile, col_idx: usize, pad_frac: f64) -> Option<Range<f64>> {
-    let null_value = match las.well_information.null.value.as_ref() {
-        Some(LasValue::Text(v)) => v.parse().unwrap(), //v.value,
-        Some(LasValue::Int(v)) => *v as f64,
-        _ => return None,
-    };
-
     let mut min = f64::INFINITY;
     let mut max = f64::NEG_INFINITY;
 
-    for row in &las.ascii_log_data.rows {
-        let val = &row[col_idx];
-        let v = val.parse::<f64>().unwrap(); //&row[col_idx];
-        if v == null_value {
-            continue;
-        }
+    // NULLs come back as None
+    for v in las.ascii_log_data.curves[col_idx].iter().flatten() {
         min = min.min(v);
         max = max.max(v);
     }
@@ -183,12 +231,6 @@ pub(crate) fn plot_las(las: &LasFile, output: &str, curves_per_row: usize) -> Re
     // Split vertically into rows
     let row_areas = root.split_evenly((num_rows, 1));
 
-    let null_value = match las.well_information.null.value.as_ref() {
-        Some(LasValue::Text(v)) => v.parse().unwrap(), //v.value,
-        Some(LasValue::Int(v)) => *v as f64,
-        _ => unreachable!(),
-    };
-
     for (row_idx, row_area) in row_areas.into_iter().enumerate() {
         let start = row_idx * curves_per_row;
         let end = (start + curves_per_row).min(curves.len());
@@ -222,15 +264,10 @@ pub(crate) fn plot_las(las: &LasFile, output: &str, curves_per_row: usize) -> Re
 
             chart.configure_mesh().disable_mesh().x_labels(6).y_labels(15).draw()?;
 
-            let series = las.ascii_log_data.rows.iter().filter_map(|row| {
-                let val = &row[curve.col_idx];
-                let v = val.parse::<f64>().unwrap(); //&row[curve.col_idx];
-                if v == null_value {
-                    None
-                } else {
-                    Some((v, row[0].parse::<f64>().unwrap()))
-                }
-            });
+            let series = las.ascii_log_data.curves[curve.col_idx]
+                .iter()
+                .zip(depths.iter())
+                .filter_map(|(v, &depth)| Some((v?, depth)));
 
             chart.draw_series(LineSeries::new(
                 series,
@@ -117,6 +117,1661 @@ fn test_no_space_before_last_colon() {
     _ = parse(file_path).unwrap();
 }
 
+#[test]
+fn test_wrapped_ascii_data() {
+    let file_path = "las_files/wrapped.las";
+    let las_file = parse(file_path).unwrap();
+    assert!(las_file.version_information.is_wrapped());
+    assert_eq!(las_file.ascii_log_data.num_rows(), 5);
+    for row in las_file.ascii_log_data.rows() {
+        assert_eq!(row.len(), las_file.curve_information.curves.len());
+    }
+    assert_eq!(las_file.ascii_log_data.row(1).unwrap()[0], "909.875");
+    assert_eq!(las_file.ascii_log_data.row(1).unwrap()[11], "18.7566");
+}
+
+#[test]
+fn test_wrapped_to_las_str_round_trip() {
+    let file_path = "las_files/_good_sample_1.las";
+    let las_file = parse(file_path).unwrap();
+    let wrapped = las_file.to_wrapped_las_str(20);
+    let data_lines = wrapped.lines().skip_while(|l| !l.starts_with("~A")).skip(1);
+    for line in data_lines {
+        assert!(line.len() <= 20, "wrapped line longer than 20 chars : {line:?}");
+    }
+
+    let dir = TestDir::new("wrapped_round_trip");
+    let out_path = dir.join("wrapped.las");
+    std::fs::write(&out_path, wrapped).unwrap();
+    let reparsed = parse(out_path.to_str().unwrap()).unwrap();
+    assert!(reparsed.version_information.is_wrapped());
+    assert_eq!(las_file.ascii_log_data.curves, reparsed.ascii_log_data.curves);
+}
+
+#[test]
+fn test_las3_section_groups() {
+    let file_path = "las_files/las3_sample.las";
+    let las_file = parse(file_path).unwrap();
+    assert!(las_file.version_information.is_las3());
+    assert_eq!(las_file.version_information.delimiter(), Delimiter::Comma);
+
+    // "Log" group maps onto the LAS 2.0 sections.
+    assert_eq!(las_file.ascii_log_data.num_rows(), 3);
+    assert_eq!(
+        las_file.ascii_log_data.row(2).unwrap(),
+        ["1669.750", "123.450", "2550.000", "12", "14"]
+    );
+    let nmr = &las_file.curve_information.curves[4];
+    assert_eq!(nmr.base_mnemonic(), "NMR");
+    assert_eq!(nmr.array_index(), Some(2));
+    assert_eq!(nmr.format.as_deref(), Some("F"));
+
+    let bht = &las_file.parameter_information.as_ref().unwrap().parameters[4];
+    assert_eq!(bht.value, LasValue::new("40.5000"));
+    assert_eq!(bht.description.as_deref(), Some("Bottom Hole Temperature"));
+    assert_eq!(bht.association.as_deref(), Some("RUN[2]"));
+
+    let core = las_file.core().unwrap();
+    assert_eq!(core.parameter.as_ref().unwrap().parameters.len(), 2);
+    assert_eq!(core.definition.as_ref().unwrap().curves.len(), 3);
+    assert_eq!(core.column("DESC").unwrap().raw(), ["Sandstone, fine grained", "Shale"]);
+    assert_eq!(
+        las_file.tops().unwrap().column("TOPN").unwrap().raw(),
+        ["Viking", "Mannville"]
+    );
+    assert_eq!(las_file.inclinometry().unwrap().data[0].num_rows(), 2);
+}
+
+#[test]
+fn test_las3_round_trip() {
+    let file_path = "las_files/las3_sample.las";
+    let mut las_file = parse(file_path).unwrap();
+
+    let dir = TestDir::new("las3_round_trip");
+    let out_path = dir.join("las3_sample.las");
+    std::fs::write(&out_path, las_file.to_las_str()).unwrap();
+    let reparsed = parse(out_path.to_str().unwrap()).unwrap();
+    assert_eq!(las_file, reparsed);
+
+    let mut json = vec![];
+    parse_into(file_path, &mut json, OutputFormat::JSON).unwrap();
+    let from_json = LasFile::try_from_json_str(std::str::from_utf8(&json).unwrap()).unwrap();
+    assert_same_values(&las_file, &from_json);
+
+    let mut yaml = vec![];
+    parse_into(file_path, &mut yaml, OutputFormat::YAML).unwrap();
+    let from_yaml = LasFile::try_from_yaml_str(std::str::from_utf8(&yaml).unwrap()).unwrap();
+    assert_same_values(&las_file, &from_yaml);
+}
+
+#[test]
+fn test_unrecognized_section() {
+    let file_path = "las_files/las3_sample.las";
+    let las = std::fs::read_to_string(file_path)
+        .unwrap()
+        .replace("~Tops_Definition", "~Tops");
+    let dir = TestDir::new("unrecognized_section");
+    let out_path = dir.join("unrecognized_section.las");
+    std::fs::write(&out_path, las).unwrap();
+    match parse(out_path.to_str().unwrap()) {
+        Err(ParseError::UnrecognizedSection { name, .. }) => assert_eq!(name, "Tops"),
+        Ok(_) => panic!("Expected ParseError::UnrecognizedSection error but got Ok"),
+        Err(e) => panic!("Expected ParseError::UnrecognizedSection error but got {e:?}"),
+    }
+}
+
+#[test]
+fn test_lenient_parse_collects_diagnostics() {
+    let file_path = "las_files/lenient.las";
+
+    match parse(file_path) {
+        Err(ParseError::SectionMissingRequiredData { .. }) => {}
+        Ok(_) => panic!("Expected ParseError::SectionMissingRequiredData error but got Ok"),
+        Err(e) => panic!("Expected ParseError::SectionMissingRequiredData error but got {e:?}"),
+    }
+
+    let (las, diagnostics) = parse_with_options(file_path, ParseOptions::lenient()).unwrap();
+    let found: Vec<(Severity, Option<usize>)> = diagnostics.iter().map(|d| (d.severity, d.line_number)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (Severity::Warning, Some(4)),  // no "UWI" or "API"
+            (Severity::Error, Some(17)),   // "BHT DEGC 35" is not a mnemonic
+            (Severity::Error, Some(19)),   // "~XTRA" is not a section we know
+            (Severity::Error, Some(23)),   // short row
+            (Severity::Error, Some(24)),   // long row
+            (Severity::Warning, Some(25)), // blank line
+            (Severity::Warning, Some(13)), // "MD" is not an allowed first curve
+        ]
+    );
+    assert!(matches!(
+        diagnostics[0].rule,
+        ParseError::SectionMissingRequiredData { .. }
+    ));
+    assert_eq!(diagnostics[3].line.as_deref(), Some("1669.875   123.450"));
+
+    // Everything we could parse is still there.
+    assert_eq!(las.parameter_information.as_ref().unwrap().parameters.len(), 1);
+    assert!(las.other_information.is_none());
+    assert_eq!(las.ascii_log_data.num_rows(), 5);
+    assert_eq!(
+        las.ascii_log_data.row(1).unwrap(),
+        vec!["1669.875", "123.450", "-999.25"]
+    );
+    assert_eq!(
+        las.ascii_log_data.row(2).unwrap(),
+        vec!["1669.750", "123.450", "2550.000"]
+    );
+    assert_eq!(las.curve("RHOB").unwrap().get(1), None);
+}
+
+#[test]
+fn test_lenient_stream_into_built_in_sinks() {
+    let file_path = "las_files/lenient.las";
+    let found = |diagnostics: Vec<Diagnostic>| diagnostics.iter().map(|d| d.line_number).collect::<Vec<_>>();
+    let expected = found(parse_with_options(file_path, ParseOptions::lenient()).unwrap().1);
+
+    let mut json = JsonSink::new(vec![]);
+    let diagnostics = stream_with_options(File::open(file_path).unwrap(), &mut json, ParseOptions::lenient()).unwrap();
+    assert_eq!(found(diagnostics), expected);
+    let json: serde_json::Value = serde_json::from_slice(&json.into_inner()).unwrap();
+    assert_eq!(json["WellInformation"]["PROV"]["value"], "ALBERTA");
+
+    let mut ndjson = NdjsonSink::new(vec![]);
+    let diagnostics =
+        stream_with_options(File::open(file_path).unwrap(), &mut ndjson, ParseOptions::lenient()).unwrap();
+    assert_eq!(found(diagnostics), expected);
+
+    let mut yaml = YamlSink::new(vec![]);
+    let diagnostics = stream_with_options(File::open(file_path).unwrap(), &mut yaml, ParseOptions::lenient()).unwrap();
+    assert_eq!(found(diagnostics), expected);
+
+    // Strict streaming still stops at the first problem.
+    assert!(matches!(
+        stream(File::open(file_path).unwrap(), &mut JsonSink::new(vec![])),
+        Err(ParseError::SectionMissingRequiredData { .. })
+    ));
+}
+
+#[test]
+fn test_lenient_parse_keeps_first_duplicate_section() {
+    let file_path = "las_files/duplicate_well_sections.las";
+    let (las, diagnostics) = parse_with_options(file_path, ParseOptions::lenient()).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert!(matches!(
+        diagnostics[0].rule,
+        ParseError::DuplicateSection {
+            section: SectionKind::Well,
+            ..
+        }
+    ));
+    assert_eq!(diagnostics[0].line_number, Some(44));
+    assert_eq!(las.well_information.line_number, 4);
+    assert!(las.other_information.is_some());
+    assert!(las.ascii_log_data.num_rows() > 0);
+}
+
+#[test]
+fn test_lenient_well_diagnostics_point_at_the_data_line() {
+    let text = std::fs::read_to_string("las_files/_good_sample_1.las").unwrap();
+    let text = text.replace("STEP .M -0.1250", "STEP .M 0.1250");
+    let (_, diagnostics) = parse_from_with_options(text.as_bytes(), ParseOptions::lenient()).unwrap();
+    assert!(matches!(diagnostics[0].rule, ParseError::InconsistentStep { .. }));
+    assert_eq!(diagnostics[0].line_number, Some(11));
+    assert_eq!(diagnostics[0].line.as_deref(), Some("STEP .M 0.1250 :STEP"));
+
+    // Missing data lines are reported on the section header.
+    let file_path = "las_files/missing_required_well_info.las";
+    let (_, diagnostics) = parse_with_options(file_path, ParseOptions::lenient()).unwrap();
+    assert!(matches!(
+        diagnostics[0].rule,
+        ParseError::WellDataMissingRequiredValueForMnemonic { .. }
+    ));
+    assert_eq!(diagnostics[0].line_number, Some(4));
+}
+
+#[test]
+fn test_las2_section_suffix_is_not_a_section_group() {
+    // "_Data" only names a section group in LAS 3.0, before that only the first letter counts.
+    let text = std::fs::read_to_string("las_files/_good_sample_1.las").unwrap();
+    let text = text.replace("~OTHER", "~Other_Data");
+    let (las, _) = parse_from_with_options(text.as_bytes(), ParseOptions::default()).unwrap();
+    assert!(las.other_information.is_some());
+    assert!(las.section_groups.is_empty());
+}
+
+#[test]
+fn test_stream_into_custom_sink() {
+    // Tracks the min/max of a single curve without ever building a LasFile.
+    #[derive(Default)]
+    struct CurveStats {
+        mnemonic: &'static str,
+        column: Option<usize>,
+        sections: Vec<SectionKind>,
+        rows: usize,
+        min: f64,
+        max: f64,
+    }
+
+    impl Sink for CurveStats {
+        fn section_start(&mut self, section: Section) -> Result<(), ParseError> {
+            self.sections.push(section.header.kind);
+            if let Some(headers) = section.ascii_headers {
+                self.column = headers.iter().position(|h| h == self.mnemonic);
+            }
+            Ok(())
+        }
+
+        fn entry(&mut self, _entry: SectionEntry) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn ascii_row(&mut self, row: &[String]) -> Result<(), ParseError> {
+            let column = self.column.ok_or_else(|| ParseError::Error {
+                message: format!("no {} curve", self.mnemonic),
+            })?;
+            let value: f64 = row[column].parse().unwrap();
+            if self.rows == 0 {
+                (self.min, self.max) = (value, value);
+            }
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+            self.rows += 1;
+            Ok(())
+        }
+
+        fn section_end(&mut self) -> Result<(), ParseError> {
+            Ok(())
+        }
+    }
+
+    let file_path = "las_files/_good_sample_1.las";
+    let mut sink = CurveStats {
+        mnemonic: "DEPT",
+        ..Default::default()
+    };
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+
+    let las = parse(file_path).unwrap();
+    let depths = las.index().unwrap().values();
+    assert_eq!(sink.rows, las.ascii_log_data.num_rows());
+    assert_eq!(sink.min, depths.iter().cloned().fold(f64::INFINITY, f64::min));
+    assert_eq!(sink.max, depths.iter().cloned().fold(f64::NEG_INFINITY, f64::max));
+    assert_eq!(sink.sections.first(), Some(&SectionKind::Version));
+    assert_eq!(sink.sections.last(), Some(&SectionKind::AsciiLogData));
+
+    // Errors from the sink stop parsing.
+    let mut sink = CurveStats {
+        mnemonic: "NOPE",
+        ..Default::default()
+    };
+    match stream(File::open(file_path).unwrap(), &mut sink) {
+        Err(ParseError::Error { message }) => assert_eq!(message, "no NOPE curve"),
+        other => panic!("Expected ParseError::Error but got {other:?}"),
+    }
+}
+
+#[test]
+fn test_csv_sink() {
+    let file_path = "las_files/_good_sample_1.las";
+    let mut csv = vec![];
+    parse_into(file_path, &mut csv, OutputFormat::CSV).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+
+    let las = parse(file_path).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "DEPT[M],DT[US/M],RHOB[K/M3],NPHI[V/V],SFLU[OHMM],SFLA[OHMM],ILM[OHMM],ILD[OHMM]"
+    );
+    assert_eq!(lines.next().unwrap(), las.ascii_log_data.row(0).unwrap().join(","));
+    assert_eq!(csv.lines().count(), las.ascii_log_data.num_rows() + 1);
+
+    let mut tsv = vec![];
+    parse_into(file_path, &mut tsv, OutputFormat::TSV).unwrap();
+    let tsv = String::from_utf8(tsv).unwrap();
+    assert_eq!(tsv, csv.replace(',', "\t"));
+}
+
+#[test]
+fn test_csv_sink_null_as_empty_with_preamble() {
+    let file_path = "las_files/wrapped.las";
+    let options = CsvOptions {
+        null_as_empty: true,
+        preamble: true,
+        ..Default::default()
+    };
+    let mut sink = CsvSink::new(vec![], options);
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    let csv = String::from_utf8(sink.into_inner()).unwrap();
+
+    let preamble: Vec<&str> = csv.lines().take_while(|line| line.starts_with('#')).collect();
+    assert_eq!(preamble.first(), Some(&"# ~VERSION INFORMATION"));
+    assert!(preamble.contains(&"# ~WELL INFORMATION"));
+    assert!(preamble.contains(&"# UWI. 100123401234W500 : UNIQUE WELL ID"));
+
+    // DT is NULL on every row
+    let rows: Vec<Vec<&str>> = csv
+        .lines()
+        .skip(preamble.len() + 1)
+        .map(|l| l.split(',').collect())
+        .collect();
+    assert_eq!(rows.len(), 5);
+    assert!(rows.iter().all(|row| row[1].is_empty() && !row[0].is_empty()));
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_to_record_batch() {
+    use arrow_array::Array;
+
+    let las = parse("las_files/wrapped.las").unwrap();
+    let batch = las.to_record_batch().unwrap();
+    assert_eq!(batch.num_columns(), las.ascii_log_data.headers.len());
+    assert_eq!(batch.num_rows(), las.ascii_log_data.num_rows());
+
+    // DT is NULL on every row
+    let schema = batch.schema();
+    let (dt_index, dt) = schema.column_with_name("DT").unwrap();
+    assert_eq!(dt.metadata().get(FIELD_METADATA_UNIT).map(String::as_str), Some("US/M"));
+    assert_eq!(batch.column(dt_index).null_count(), batch.num_rows());
+    assert_eq!(batch.column(0).null_count(), 0);
+
+    let well: sections::WellInformation = serde_json::from_str(&schema.metadata()[METADATA_WELL]).unwrap();
+    assert_eq!(well, las.well_information);
+    assert!(schema.metadata().contains_key(METADATA_CURVES));
+    assert!(schema.metadata().contains_key(METADATA_VERSION));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn test_parquet_sink() {
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let file_path = "las_files/_good_sample_1.las";
+    let dir = TestDir::new("parquet_sink");
+    let out_path = dir.join("sample.parquet");
+    let out = File::create(&out_path).unwrap();
+    let mut sink = ParquetSink::new(out, ParquetOptions { row_group_size: 2 });
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+
+    let las = parse(file_path).unwrap();
+    let expected = las.to_record_batch().unwrap();
+
+    let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&out_path).unwrap()).unwrap();
+    assert_eq!(
+        builder.metadata().num_row_groups(),
+        las.ascii_log_data.num_rows().div_ceil(2)
+    );
+    assert_eq!(
+        builder.schema().metadata()[METADATA_WELL],
+        expected.schema().metadata()[METADATA_WELL]
+    );
+
+    // Reads across row groups
+    let batches: Vec<_> = builder
+        .with_batch_size(1024)
+        .build()
+        .unwrap()
+        .map(|b| b.unwrap())
+        .collect();
+    assert_eq!(batches.len(), 1);
+    assert_eq!(batches[0].columns(), expected.columns());
+
+    // The closed file can be taken back out of the sink, eg. to keep it in memory.
+    let mut sink = ParquetSink::new(vec![], ParquetOptions { row_group_size: 2 });
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    std::fs::write(&out_path, sink.into_inner().unwrap()).unwrap();
+    let batches: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(File::open(&out_path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap()
+        .map(|b| b.unwrap())
+        .collect();
+    assert_eq!(batches[0].num_rows(), las.ascii_log_data.num_rows());
+}
+
+#[test]
+fn test_las_file_builder() {
+    let mut las = LasFileBuilder::new()
+        .well_info("WELL", "ANY ET AL 12-34", "WELL")
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+        .parameter("BHT", "DEGC", "35.5", "BOTTOM HOLE TEMPERATURE")
+        .curve("DEPT", "M", "DEPTH")
+        .curve("GR", "API", "GAMMA RAY")
+        .row([1670.0, 81.25])
+        .row([Some(1670.1), None])
+        .rows([[1670.2, 79.0], [1670.3, 80.5]])
+        .build()
+        .unwrap();
+
+    let well = &las.well_information;
+    assert_eq!(well.strt.value, LasValue::new("1670"));
+    assert_eq!(well.stop.value, LasValue::new("1670.3"));
+    assert_eq!(well.step.value, LasValue::new("0.1"));
+    assert_eq!(well.step.unit.as_deref(), Some("M"));
+    assert_eq!(well.uwi.as_ref().unwrap().value, LasValue::new("100123401234W500"));
+    assert_eq!(las.curve("GR").unwrap().get(1), None);
+    assert_eq!(las.ascii_log_data.row(1).unwrap(), vec!["1670.1", "-999.25"]);
+
+    // What we build is what we parse.
+    let dir = TestDir::new("las_file_builder");
+    let out_path = dir.join("built.las");
+    std::fs::write(&out_path, las.to_las_str()).unwrap();
+    assert_eq!(parse(out_path.to_str().unwrap()).unwrap(), las);
+
+    // Irregular spacing means STEP 0.
+    let las = LasFileBuilder::new()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("API", "12345", "API NUMBER")
+        .decimals(4)
+        .curve("DEPTH", "FT", "")
+        .rows([[100.0], [100.5], [102.0]])
+        .build()
+        .unwrap();
+    assert_eq!(las.well_information.step.value, LasValue::new("0.0000"));
+    assert_eq!(las.well_information.stop.value, LasValue::new("102.0000"));
+}
+
+#[test]
+fn test_las_file_builder_validates() {
+    let builder = LasFileBuilder::new()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .curve("DEPT", "M", "DEPTH")
+        .row([1670.0]);
+    match builder.clone().build() {
+        Err(ParseError::SectionMissingRequiredData { one_of, .. }) => assert_eq!(one_of, vec!["UWI", "API"]),
+        other => panic!("Expected ParseError::SectionMissingRequiredData but got {other:?}"),
+    }
+    match builder.well_info("UWI", "1", "").row([1670.5, 1.0]).build() {
+        Err(ParseError::AsciiColumnsMismatch { line_number, .. }) => assert_eq!(line_number, 2),
+        other => panic!("Expected ParseError::AsciiColumnsMismatch but got {other:?}"),
+    }
+    match LasFileBuilder::new().curve("GR", "API", "").build() {
+        Err(ParseError::DisallowedFirstCurve { got, .. }) => assert_eq!(got, "GR"),
+        other => panic!("Expected ParseError::DisallowedFirstCurve but got {other:?}"),
+    }
+}
+
+#[test]
+fn test_las_file_builder_from_json_rows() {
+    let file_path = "las_files/wrapped.las";
+    let mut sink = NdjsonSink::new(vec![]);
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    let ndjson = sink.into_inner();
+
+    let las = LasFileBuilder::from_json_rows(ndjson.as_slice())
+        .unwrap()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+        .build()
+        .unwrap();
+    let original = parse(file_path).unwrap();
+    assert_eq!(las.curve_information.curves, original.curve_information.curves);
+    for (curve, original_curve) in las.ascii_log_data.curves.iter().zip(&original.ascii_log_data.curves) {
+        assert_eq!(
+            curve.iter().collect::<Vec<_>>(),
+            original_curve.iter().collect::<Vec<_>>()
+        );
+    }
+
+    // Without a header record, curves are the keys of the first row.
+    let rows = "{\"DEPT\":1670.0,\"GR\":45.2}\n{\"GR\":null,\"DEPT\":1670.5}\n";
+    let las = LasFileBuilder::from_json_rows(rows.as_bytes())
+        .unwrap()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+        .build()
+        .unwrap();
+    assert_eq!(las.ascii_log_data.headers, ["DEPT", "GR"]);
+    assert_eq!(las.curve("GR").unwrap().iter().collect::<Vec<_>>(), [Some(45.2), None]);
+    assert!(matches!(
+        LasFileBuilder::from_json_rows("{\"DEPT\":\"deep\"}".as_bytes()),
+        Err(ParseError::InvalidAsciiValue { line_number: 1, .. })
+    ));
+
+    // Every row needs a depth.
+    let rows = "{\"DEPT\":1670.0,\"GR\":45.2}\n{\"GR\":45.3}\n{\"DEPT\":1671.0,\"GR\":45.4}\n";
+    let built = LasFileBuilder::from_json_rows(rows.as_bytes())
+        .unwrap()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+        .build();
+    match built {
+        Err(ParseError::Error { message }) => assert!(message.starts_with("Row 2 has no \"DEPT\" value"), "{message}"),
+        other => panic!("Expected ParseError::Error but got {other:?}"),
+    }
+}
+
+#[test]
+fn test_las_file_builder_from_csv() {
+    let file_path = "las_files/wrapped.las";
+    let options = CsvOptions {
+        null_as_empty: true,
+        preamble: true,
+        ..Default::default()
+    };
+    let mut sink = CsvSink::new(vec![], options);
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    let csv = sink.into_inner();
+
+    let las = LasFileBuilder::from_csv(csv.as_slice(), ',')
+        .unwrap()
+        .well_info("PROV", "ALBERTA", "PROVINCE")
+        .well_info("UWI", "100123401234W500", "UNIQUE WELL ID")
+        .build()
+        .unwrap();
+    let original = parse(file_path).unwrap();
+
+    let units: Vec<_> = las.curve_information.curves.iter().map(|c| c.unit.clone()).collect();
+    let original_units: Vec<_> = original
+        .curve_information
+        .curves
+        .iter()
+        .map(|c| c.unit.clone())
+        .collect();
+    assert_eq!(units, original_units);
+    for (curve, original_curve) in las.ascii_log_data.curves.iter().zip(&original.ascii_log_data.curves) {
+        assert_eq!(
+            curve.iter().collect::<Vec<_>>(),
+            original_curve.iter().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_las_writer_round_trip() {
+    let dir = TestDir::new("las_writer");
+    for file_path in [
+        "las_files/_good_sample_1.las",
+        "las_files/wrapped.las",
+        "las_files/las3_sample.las",
+    ] {
+        let las = parse(file_path).unwrap();
+        let mut writer = LasWriter::new(vec![], LasWriterOptions::pretty());
+        writer.write(&las).unwrap();
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+        let out_path = dir.join(file_path.rsplit('/').next().unwrap());
+        std::fs::write(&out_path, out).unwrap();
+        assert_eq!(parse(out_path.to_str().unwrap()).unwrap(), las, "{file_path}");
+    }
+}
+
+#[test]
+fn test_las_writer_options() {
+    let las = parse("las_files/_good_sample_1.las").unwrap();
+    let options = LasWriterOptions {
+        align_data_lines: true,
+        align_columns: true,
+        decimals: Some(2),
+        line_ending: LineEnding::CRLF,
+        uppercase_headers: true,
+        encoding: Encoding::Utf8,
+    };
+    let mut writer = LasWriter::new(vec![], options);
+    writer.write(&las).unwrap();
+    let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+
+    assert!(!out.replace("\r\n", "").contains('\n'));
+    let lines: Vec<&str> = out.split("\r\n").collect();
+    assert!(lines.contains(&"~VERSION INFORMATION"));
+    assert!(lines.contains(&"VERS. 2.0        : CWLS LOG ASCII STANDARD -VERSION 2.0"));
+    assert!(lines.contains(&"FLD .  WILDCAT                  : FIELD"));
+    assert!(lines.contains(&"DT  .US/M 60 520 32 00 : 2 SONIC TRANSIT TIME"));
+
+    // Right aligned to the widest value in each column, without trailing whitespace.
+    let a = lines.iter().position(|l| l.starts_with("~A")).unwrap();
+    assert_eq!(lines[a + 1], "1670.00 123.45 2550.00 0.45 123.45 123.45 110.20   5.60");
+    assert_eq!(lines[a + 1].len(), lines[a + 2].len());
+
+    let las = parse("las_files/4771-36-SESE.las").unwrap();
+    let options = LasWriterOptions {
+        uppercase_headers: true,
+        ..Default::default()
+    };
+    let mut writer = LasWriter::new(vec![], options);
+    writer.write(&las).unwrap();
+    let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+    assert!(out.lines().any(|l| l == "~WELL INFORMATION BLOCK"));
+}
+
+#[test]
+fn test_las_document_edits_only_touch_their_line() {
+    let file_path = "las_files/00-01-01-073-05W5-0.las";
+    let original = std::fs::read_to_string(file_path).unwrap();
+    let mut doc = LasDocument::open(file_path).unwrap();
+    assert_eq!(doc.to_las_str(), original);
+
+    doc.set_value(SectionKind::Well, "comp", "NEW COMPANY").unwrap_err();
+    doc.set_value(SectionKind::Well, "stop", "650.25").unwrap();
+    doc.set_value(SectionKind::Version, "VERS", "2.0").unwrap();
+    doc.set_value(SectionKind::Parameter, "RUN", "TWO, AFTER A LONG RUN IN")
+        .unwrap();
+    doc.set_cell(1, 3, "-999.25").unwrap();
+    doc.set_cell(2, 0, "390.5").unwrap();
+    doc.set_cell(2, 6, "1").unwrap_err();
+    doc.set_cell(2, 1, "1 2").unwrap_err();
+
+    let edited = doc.to_las_str();
+    let changed: Vec<(&str, &str)> = original
+        .lines()
+        .zip(edited.lines())
+        .filter(|(before, after)| before != after)
+        .collect();
+    assert_eq!(
+        changed,
+        [
+            (
+                "STOP.M        650                            : bot_depth    - STOP DEPTH",
+                "STOP.M        650.25                         : bot_depth    - STOP DEPTH"
+            ),
+            (
+                "RUN .         ONE                  : Run          - RUN NUMBER",
+                "RUN .         TWO, AFTER A LONG RUN IN : Run          - RUN NUMBER"
+            ),
+            (
+                "  390.250    0.208    0.456   86.413  238.331    2.925",
+                "  390.250    0.208    0.456  -999.25  238.331    2.925"
+            ),
+            (
+                "  390.500    0.246    0.452   90.229  238.069    2.917",
+                "    390.5    0.246    0.452   90.229  238.069    2.917"
+            ),
+        ]
+    );
+    assert_eq!(original.lines().count(), edited.lines().count());
+    assert_eq!(original.matches("\r\n").count(), edited.matches("\r\n").count());
+
+    let las = doc.to_las_file().unwrap();
+    assert_eq!(las.well_information.stop.value, LasValue::new("650.25"));
+    assert_eq!(las.curve("GR").unwrap().get(1), None);
+}
+
+#[test]
+fn test_las_document_wrapped_cells() {
+    let file_path = "las_files/wrapped.las";
+    let mut doc = LasDocument::open(file_path).unwrap();
+    // Row 0 is spread over 3 lines, the last value of it is on the third.
+    doc.set_cell(0, 11, "0.5").unwrap();
+    doc.set_cell(1, 0, "909.875").unwrap();
+
+    let las = doc.to_las_file().unwrap();
+    let original = parse(file_path).unwrap();
+    assert_eq!(las.ascii_log_data.row(0).unwrap()[11], "0.5");
+    assert_eq!(las.ascii_log_data.row(1), original.ascii_log_data.row(1));
+    assert_eq!(las.ascii_log_data.num_rows(), original.ascii_log_data.num_rows());
+}
+
+#[test]
+fn test_typed_well_values() {
+    let file_path = "las_files/_good_sample_1.las";
+    let mut las_file = parse(file_path).unwrap();
+    let well = &mut las_file.well_information;
+
+    assert_eq!(well.start(), Some(1670.0));
+    assert_eq!(well.stop(), Some(1669.75));
+    assert_eq!(well.step(), Some(-0.125));
+    assert_eq!(well.null_value(), Some(-999.25));
+    assert_eq!(well.date(), chrono::NaiveDate::from_ymd_opt(1986, 12, 13));
+
+    // Floats keep the text they came from.
+    let Some(LasValue::Float(strt)) = well.strt.value.as_ref() else {
+        panic!("Expected STRT to be a float, got {:?}", well.strt.value);
+    };
+    assert_eq!(strt.as_str(), "1670.0000");
+    assert!(las_file.to_las_str().contains("STRT.M 1670.0000 : START DEPTH"));
+    let json = las_file.to_json_str().unwrap();
+    assert_eq!(LasFile::try_from_json_str(&json).unwrap(), las_file);
+
+    let well = &mut las_file.well_information;
+    well.step.value = LasValue::new("0.1250");
+    match well.validate() {
+        Err(ParseError::InconsistentStep { step, .. }) => assert_eq!(step, 0.125),
+        other => panic!("Expected ParseError::InconsistentStep error but got {other:?}"),
+    }
+    // Zero means irregular spacing.
+    well.step.value = LasValue::new("0");
+    well.validate().unwrap();
+
+    // JSON numbers read back as numbers.
+    let value: LasValue = serde_json::from_str("2.5").unwrap();
+    assert_eq!(value.as_f64(), Some(2.5));
+}
+
+#[test]
+fn test_convert_units() {
+    let file_path = "las_files/_good_sample_1.las";
+    let mut las_file = parse(file_path).unwrap();
+
+    las_file.convert_depth_units("ft").unwrap();
+    let well = &las_file.well_information;
+    assert_eq!(well.strt.value, LasValue::new("5479.0026"));
+    assert_eq!(well.stop.value, LasValue::new("5478.1824"));
+    assert_eq!(well.step.value, LasValue::new("-0.4101"));
+    assert_eq!(well.strt.unit.as_deref(), Some("FT"));
+    assert_eq!(las_file.curve_information.curves[0].unit.as_deref(), Some("FT"));
+    assert_eq!(las_file.index().unwrap().raw()[0], "5479.0026");
+    // Only lines in the same unit as the index are converted.
+    let csgl = &las_file.parameter_information.as_ref().unwrap().parameters[2];
+    assert_eq!(
+        (csgl.value.clone(), csgl.unit.as_deref()),
+        (LasValue::new("408.7927"), Some("FT"))
+    );
+    let bht = &las_file.parameter_information.as_ref().unwrap().parameters[1];
+    assert_eq!(bht.value, LasValue::new("35.5000"));
+
+    las_file.convert_units("rhob", "g/cc").unwrap();
+    assert_eq!(las_file.curve_information.curves[2].unit.as_deref(), Some("G/C3"));
+    assert_eq!(las_file.curve("RHOB").unwrap().raw()[0], "2.550000");
+
+    las_file.convert_units("DT", "US/F").unwrap();
+    assert_eq!(las_file.curve("DT").unwrap().raw()[0], "37.6276");
+
+    match las_file.convert_units("RHOB", "FT") {
+        Err(ParseError::IncompatibleUnits { from, to }) => assert_eq!((from.as_str(), to.as_str()), ("G/C3", "FT")),
+        other => panic!("Expected ParseError::IncompatibleUnits error but got {other:?}"),
+    }
+    assert!(matches!(
+        las_file.convert_units("RHOB", "STONE"),
+        Err(ParseError::UnknownUnit { .. })
+    ));
+    // Ohms measure resistance, not resistivity.
+    assert!(Unit::find("OHMS").is_none());
+
+    // NULLs stay NULL.
+    let mut las_file = parse("las_files/00-01-01-073-05W5-0.las").unwrap();
+    let nulls: Vec<bool> = (0..las_file.ascii_log_data.num_rows())
+        .map(|i| las_file.curve("GR").unwrap().is_null(i))
+        .collect();
+    las_file.convert_depth_units("FT").unwrap();
+    las_file.convert_units("CALI", "IN").unwrap();
+    let cali = las_file.curve("CALI").unwrap();
+    assert_eq!(cali.raw()[0], "9.38500");
+    assert_eq!(
+        nulls,
+        (0..las_file.ascii_log_data.num_rows())
+            .map(|i| las_file.curve("GR").unwrap().is_null(i))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_resample() {
+    let file_path = "las_files/_good_sample_1.las";
+    let mut las_file = parse(file_path).unwrap();
+    // Interpolated values aren't rounded to the decimals of the curve.
+    for (row, ilm) in ["110", "111", "112"].into_iter().enumerate() {
+        las_file.ascii_log_data.curves[6].set(row, ilm.to_string());
+    }
+    las_file.resample(0.0625, ResampleMethod::Linear).unwrap();
+    let well = &las_file.well_information;
+    assert_eq!(well.strt.value, LasValue::new("1670.0000"));
+    assert_eq!(well.stop.value, LasValue::new("1669.7500"));
+    assert_eq!(well.step.value, LasValue::new("-0.0625"));
+    let ild = las_file.curve("ILD").unwrap();
+    assert_eq!(
+        las_file.index().unwrap().raw(),
+        ["1670.0000", "1669.9375", "1669.8750", "1669.8125", "1669.7500"]
+    );
+    assert_eq!(ild.raw(), ["5.6000", "5.6000", "5.6000", "55.6000", "105.6000"]);
+    assert_eq!(
+        las_file.curve("ILM").unwrap().raw(),
+        ["110.0000", "110.5000", "111.0000", "111.5000", "112.0000"]
+    );
+
+    let mut las_file = parse(file_path).unwrap();
+    las_file.resample(0.25, ResampleMethod::BlockAverage).unwrap();
+    assert_eq!(las_file.well_information.step.value, LasValue::new("-0.250"));
+    assert_eq!(las_file.index().unwrap().raw(), ["1670.000", "1669.750"]);
+    // Halfway between the two new depths is 1669.875, which belongs to the upper block.
+    assert_eq!(las_file.curve("ILD").unwrap().raw(), ["5.600", "105.600"]);
+
+    // NULLs aren't interpolated, and depths outside the original index are NULL.
+    let mut las_file = parse(file_path).unwrap();
+    las_file.ascii_log_data.curves[7].set(1, "-999.25".to_string());
+    las_file
+        .resample_to(&[1670.0, 1669.9, 1669.8, 1669.6], ResampleMethod::Linear)
+        .unwrap();
+    let ild: Vec<Option<f64>> = las_file.curve("ILD").unwrap().iter().collect();
+    assert_eq!(ild, [Some(5.6), None, None, None]);
+    assert_eq!(las_file.well_information.step.value, LasValue::new("0.000"));
+
+    // NULL values are written as the Well section "NULL" text, or -999.25 when there is none.
+    let resampled_nulls = |null: Option<&str>| {
+        let mut las_file = parse(file_path).unwrap();
+        las_file.well_information.null.value = null.and_then(LasValue::new);
+        las_file
+            .ascii_log_data
+            .set_null_value(null.and_then(|n| n.parse().ok()));
+        las_file.resample_to(&[1670.0, 1669.6], ResampleMethod::Linear).unwrap();
+        (
+            las_file.well_information.null.value.clone(),
+            las_file.curve("ILD").unwrap().clone(),
+        )
+    };
+    let (null, ild) = resampled_nulls(Some("-999.2500"));
+    assert_eq!(null, LasValue::new("-999.2500"));
+    assert_eq!(ild.raw()[1], "-999.2500");
+    let (null, ild) = resampled_nulls(None);
+    assert_eq!(null, LasValue::new("-999.25"));
+    assert_eq!(ild.raw()[1], "-999.25");
+    assert!(ild.is_null(1));
+    let nearest = {
+        let mut las_file = parse(file_path).unwrap();
+        las_file
+            .resample_to(&[1669.9, 1669.8], ResampleMethod::Nearest)
+            .unwrap();
+        las_file.ascii_log_data
+    };
+    assert_eq!(nearest.curve("ILD").unwrap().raw(), ["5.600", "105.600"]);
+}
+
+#[test]
+fn test_merge_runs() {
+    let file_path = "las_files/_good_sample_1.las";
+    // A deeper run, overlapping the bottom two depths of the main run, without "ILD".
+    let deep_run = || {
+        let mut las_file = parse(file_path).unwrap();
+        for (i, depth) in ["1669.875", "1669.750", "1669.625"].into_iter().enumerate() {
+            las_file.ascii_log_data.curves[0].set(i, depth.to_string());
+            las_file.ascii_log_data.curves[4].set(i, "1.000".to_string());
+        }
+        las_file.curve_information.curves.pop();
+        las_file.ascii_log_data.curves.pop();
+        las_file.ascii_log_data.headers.pop();
+        LasRun::new("deep.las", las_file)
+    };
+    let runs = || vec![LasRun::open(file_path).unwrap(), deep_run()];
+
+    let (merged, diagnostics) = LasFile::merge(runs(), &MergeOptions::default()).unwrap();
+    assert!(diagnostics.is_empty());
+    assert_eq!(
+        merged.index().unwrap().raw(),
+        ["1670.000", "1669.875", "1669.750", "1669.625"]
+    );
+    assert_eq!(
+        merged.curve("SFLU").unwrap().raw(),
+        ["123.450", "123.450", "123.450", "1.000"]
+    );
+    // Neither run has "ILD" at 1669.625
+    assert_eq!(
+        merged.curve("ILD").unwrap().raw(),
+        ["05.600", "05.600", "105.600", "-999.25"]
+    );
+    assert_eq!(merged.well_information.stop.value, LasValue::new("1669.625"));
+    let other = merged.other_information.as_ref().unwrap();
+    let provenance: Vec<&str> = other.data.iter().rev().take(2).rev().map(|d| d.text.as_str()).collect();
+    assert_eq!(
+        provenance,
+        [
+            "Interval 1670.000 - 1669.750 M from _good_sample_1.las",
+            "Interval 1669.625 - 1669.625 M from deep.las"
+        ]
+    );
+
+    // The deep run wins the overlap, "ILD" is filled in from the main run.
+    let options = MergeOptions {
+        priority: MergePriority::Last,
+        ..Default::default()
+    };
+    let (merged, _) = LasFile::merge(runs(), &options).unwrap();
+    assert_eq!(
+        merged.curve("SFLU").unwrap().raw(),
+        ["123.450", "1.000", "1.000", "1.000"]
+    );
+    assert_eq!(
+        merged.curve("ILD").unwrap().raw(),
+        ["05.600", "05.600", "105.600", "-999.25"]
+    );
+    assert_eq!(merged.well_information.step.value, LasValue::new("-0.125"));
+}
+
+#[test]
+fn test_merge_mismatched_runs() {
+    let file_path = "las_files/_good_sample_1.las";
+    let runs = || {
+        let mut other = LasRun::open(file_path).unwrap();
+        other.name = "other.las".to_string();
+        other.las_file.well_information.uwi.as_mut().unwrap().value = LasValue::new("100123401234W600");
+        other.las_file.convert_depth_units("FT").unwrap();
+        vec![LasRun::open(file_path).unwrap(), other]
+    };
+
+    let err = LasFile::merge(runs(), &MergeOptions::default()).unwrap_err();
+    assert!(matches!(err, ParseError::MismatchedRuns { ref field, .. } if field == "UWI"));
+
+    let options = MergeOptions {
+        strictness: Strictness::Lenient,
+        ..Default::default()
+    };
+    let (merged, diagnostics) = LasFile::merge(runs(), &options).unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    // Converted back to the depth unit of the first run.
+    assert_eq!(merged.curve_information.curves[0].unit.as_deref(), Some("M"));
+    assert_eq!(merged.index().unwrap().values(), [1670.0, 1669.875, 1669.75]);
+}
+
+#[test]
+fn test_merge_mismatched_curve_units() {
+    let file_path = "las_files/_good_sample_1.las";
+    // A deeper run with "RHOB" in G/C3.
+    let deep_run = || {
+        let mut las_file = parse(file_path).unwrap();
+        for (i, depth) in ["1669.875", "1669.750", "1669.625"].into_iter().enumerate() {
+            las_file.ascii_log_data.curves[0].set(i, depth.to_string());
+        }
+        las_file.convert_units("RHOB", "G/C3").unwrap();
+        LasRun::new("deep.las", las_file)
+    };
+
+    // Converted back to the unit of the first run.
+    let runs = vec![LasRun::open(file_path).unwrap(), deep_run()];
+    let (merged, diagnostics) = LasFile::merge(runs, &MergeOptions::default()).unwrap();
+    assert!(diagnostics.is_empty());
+    assert_eq!(merged.curve_information.curves[2].unit.as_deref(), Some("K/M3"));
+    assert_eq!(merged.curve("RHOB").unwrap().values()[3], 2550.0);
+
+    // Units we don't know can't be converted.
+    let runs = || {
+        let mut deep_run = deep_run();
+        deep_run.las_file.curve_information.curves[2].unit = Some("LB/BBL".to_string());
+        vec![LasRun::open(file_path).unwrap(), deep_run]
+    };
+    let err = LasFile::merge(runs(), &MergeOptions::default()).unwrap_err();
+    assert!(matches!(err, ParseError::MismatchedRuns { ref field, .. } if field == "RHOB unit"));
+    let options = MergeOptions {
+        strictness: Strictness::Lenient,
+        ..Default::default()
+    };
+    let (_, diagnostics) = LasFile::merge(runs(), &options).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+}
+
+#[test]
+fn test_slice_depth_and_select_curves() {
+    let file_path = "las_files/_good_sample_1.las";
+    let mut las_file = parse(file_path).unwrap();
+    // Either way round
+    las_file.slice_depth(1669.9, 1669.75).unwrap();
+    las_file.select_curves(&["ild", "DT"]).unwrap();
+    assert_eq!(las_file.index().unwrap().raw(), ["1669.875", "1669.750"]);
+    assert_eq!(las_file.ascii_log_data.headers, ["DEPT", "DT", "ILD"]);
+    let mnemonics: Vec<&str> = las_file
+        .curve_information
+        .curves
+        .iter()
+        .map(|c| c.mnemonic.as_str())
+        .collect();
+    assert_eq!(mnemonics, ["DEPT", "DT", "ILD"]);
+    assert_eq!(las_file.curve("ILD").unwrap().raw(), ["05.600", "105.600"]);
+    assert_eq!(las_file.well_information.strt.value, LasValue::new("1669.875"));
+    assert_eq!(las_file.well_information.stop.value, LasValue::new("1669.750"));
+
+    assert!(las_file.select_curves(&["GR"]).is_err());
+    assert!(las_file.slice_depth(1200.0, 1450.0).is_err());
+}
+
+#[test]
+fn test_filter_sink() {
+    let file_path = "las_files/_good_sample_1.las";
+    let options = FilterOptions {
+        top: Some(1669.8),
+        base: None,
+        curves: Some(vec!["ILD".to_string(), "DT".to_string()]),
+    };
+    let mut sink = FilterSink::new(AstSink::new(), options.clone());
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    let streamed = LasFile::try_from(sink.into_inner()).unwrap();
+
+    let mut las_file = parse(file_path).unwrap();
+    las_file.slice_depth(1669.8, f64::INFINITY).unwrap();
+    las_file.select_curves(&["ILD", "DT"]).unwrap();
+    assert_eq!(streamed.curve_information, las_file.curve_information);
+    assert_eq!(streamed.ascii_log_data, las_file.ascii_log_data);
+    // Worked out from "STEP" instead of the rows, so it keeps the precision of "STRT".
+    assert_eq!(streamed.well_information.stop.value, LasValue::new("1669.8750"));
+
+    let csv = CsvSink::new(vec![], CsvOptions::default());
+    let options = FilterOptions {
+        curves: Some(vec!["GR".to_string()]),
+        ..options
+    };
+    assert!(stream(File::open(file_path).unwrap(), &mut FilterSink::new(csv, options)).is_err());
+}
+
+#[test]
+fn test_validate_data() {
+    let file_path = "las_files/inconsistent_depths.las";
+    // Opt in only
+    let (_, diagnostics) = parse_with_options(file_path, ParseOptions::lenient()).unwrap();
+    assert!(diagnostics.is_empty());
+
+    let options = ParseOptions {
+        validate_data: true,
+        ..ParseOptions::lenient()
+    };
+    let (las_file, diagnostics) = parse_with_options(file_path, options.clone()).unwrap();
+    let found: Vec<(Severity, Option<usize>)> = diagnostics.iter().map(|d| (d.severity, d.line_number)).collect();
+    assert_eq!(
+        found,
+        vec![
+            (Severity::Warning, Some(18)), // first depth isn't "STRT"
+            (Severity::Warning, Some(19)), // so the next one isn't a step away from it
+            (Severity::Warning, Some(20)), // two steps at once
+            (Severity::Warning, Some(21)), // heads back up
+            (Severity::Warning, Some(22)), // not one step from the one before
+            (Severity::Warning, Some(22)), // last depth isn't "STOP"
+        ]
+    );
+    assert!(matches!(diagnostics[0].rule, ParseError::DepthMismatch { ref mnemonic, .. } if mnemonic == "STRT"));
+    assert!(matches!(diagnostics[3].rule, ParseError::NonMonotonicIndex { .. }));
+    assert!(matches!(diagnostics[5].rule, ParseError::DepthMismatch { ref mnemonic, .. } if mnemonic == "STOP"));
+
+    // Streaming finds the same problems, and the parsed file does too, without line numbers.
+    let streamed = stream_with_options(File::open(file_path).unwrap(), &mut AstSink::new(), options).unwrap();
+    assert_eq!(streamed.len(), diagnostics.len());
+    let well = &las_file.well_information;
+    assert_eq!(
+        well.data_validation_errors(&las_file.ascii_log_data).len(),
+        diagnostics.len()
+    );
+    assert!(
+        parse_with_options(
+            file_path,
+            ParseOptions {
+                validate_data: true,
+                ..Default::default()
+            }
+        )
+        .is_err()
+    );
+
+    // Evenly spaced depths with "STEP 0"
+    let mut las_file = parse("las_files/_good_sample_1.las").unwrap();
+    assert!(
+        las_file
+            .well_information
+            .validate_data(&las_file.ascii_log_data)
+            .is_ok()
+    );
+    las_file.well_information.step.value = LasValue::new("0");
+    let errors = las_file
+        .well_information
+        .data_validation_errors(&las_file.ascii_log_data);
+    assert!(matches!(errors[..], [ParseError::UndeclaredStep { step }] if step == -0.125));
+}
+
+#[test]
+fn test_json_sink_values() {
+    let las = std::fs::read_to_string("las_files/_good_sample_1.las")
+        .unwrap()
+        .replace("1669.875 123.450 2550.000", "1669.875 -999.25 a\"b\\c");
+    let mut sink = JsonSink::new(vec![]);
+    stream(las.as_bytes(), &mut sink).unwrap();
+    let rows_json = sink.into_inner();
+    let json: serde_json::Value = serde_json::from_slice(&rows_json).unwrap();
+    let rows = &json["AsciiLogData"]["rows"];
+    assert_eq!(rows[0][0], serde_json::json!(1670.0));
+    // Numbers JSON doesn't allow as written, eg. "05.600", are still numbers.
+    assert_eq!(rows[0][7], serde_json::json!(5.6));
+    assert_eq!(rows[1][1], serde_json::Value::Null);
+    assert_eq!(rows[1][2], serde_json::json!("a\"b\\c"));
+
+    let options = JsonOptions { columnar: true };
+    let mut sink = JsonSink::with_options(vec![], options);
+    stream(las.as_bytes(), &mut sink).unwrap();
+    let columnar = sink.into_inner();
+    let json: serde_json::Value = serde_json::from_slice(&columnar).unwrap();
+    let columns = &json["AsciiLogData"]["columns"];
+    assert_eq!(columns["DEPT"], serde_json::json!([1670.0, 1669.875, 1669.75]));
+    assert_eq!(columns["DT"], serde_json::json!([123.45, null, 123.45]));
+
+    // Reads back either way, with NULL in place of null.
+    let from_rows = LasFile::try_from_json_str(std::str::from_utf8(&rows_json).unwrap()).unwrap();
+    let from_columns = LasFile::try_from_json_str(std::str::from_utf8(&columnar).unwrap()).unwrap();
+    assert_eq!(from_rows.ascii_log_data.curves[1].raw()[1], "-999.25");
+    // Numbers keep their original text.
+    assert_eq!(from_rows.ascii_log_data.curves[0].raw()[0], "1670.000");
+    assert_same_values(&from_rows, &from_columns);
+}
+
+#[test]
+fn test_ndjson() {
+    let mut ndjson = vec![];
+    parse_into("las_files/las3_sample.las", &mut ndjson, OutputFormat::NDJSON).unwrap();
+    let lines: Vec<serde_json::Value> = std::str::from_utf8(&ndjson)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 4);
+    let header = lines[0].as_object().unwrap();
+    let sections: Vec<&str> = header.keys().map(String::as_str).collect();
+    assert!(sections.contains(&"WellInformation") && sections.contains(&"CurveInformation"));
+    assert_eq!(header["WellInformation"]["STRT"]["value"], "1670.0000");
+    assert_eq!(
+        lines[2],
+        serde_json::json!({"DEPT":1669.875,"DT":123.45,"RHOB":2550.0,"NMR[1]":11,"NMR[2]":13})
+    );
+}
+
+#[test]
+fn test_las_from_json_and_yaml() {
+    let to_las = |export: &[u8], format: OutputFormat| {
+        let mut writer = LasWriter::new(vec![], LasWriterOptions::default());
+        match format {
+            OutputFormat::JSON => writer.write_json(export).unwrap(),
+            _ => writer.write_yaml(export).unwrap(),
+        }
+        let las = writer.into_inner().unwrap();
+        LasDocument::from_reader(las.as_slice()).unwrap().to_las_file().unwrap()
+    };
+
+    let file_path = "las_files/las3_sample.las";
+    let las_file = parse(file_path).unwrap();
+    for format in [OutputFormat::JSON, OutputFormat::YAML] {
+        let mut export = vec![];
+        parse_into(file_path, &mut export, format.clone()).unwrap();
+        assert_eq!(to_las(&export, format), las_file);
+    }
+
+    // Neither of these can be streamed, rows come before the Curve section or are columnar.
+    let file_path = "las_files/_good_sample_1.las";
+    let mut las_file = parse(file_path).unwrap();
+    let json = las_file.to_json_str().unwrap();
+    assert_eq!(to_las(json.as_bytes(), OutputFormat::JSON), las_file);
+
+    let mut sink = JsonSink::with_options(vec![], JsonOptions { columnar: true });
+    stream(File::open(file_path).unwrap(), &mut sink).unwrap();
+    assert_same_values(&to_las(&sink.into_inner(), OutputFormat::JSON), &las_file);
+
+    // A short row, before the Well section so it can't be streamed, is padded with NULL.
+    let mut json: serde_json::Value = serde_json::from_str(&las_file.to_json_str().unwrap()).unwrap();
+    let mut ascii_log_data = json.as_object_mut().unwrap().remove("AsciiLogData").unwrap();
+    ascii_log_data["rows"][1].as_array_mut().unwrap().truncate(2);
+    let json = format!("{{\"AsciiLogData\":{ascii_log_data},{}", &json.to_string()[1..]);
+    let las = to_las(json.as_bytes(), OutputFormat::JSON);
+    assert_eq!(las.ascii_log_data.row(1).unwrap()[1..3], ["123.450", "-999.25"]);
+}
+
+#[test]
+fn test_ragged_ascii_log_data() {
+    let mut data = sections::AsciiLogData::new(sections::AsciiLogDataParams {
+        headers: vec!["DEPT".to_string(), "GR".to_string()],
+        rows: vec![
+            vec!["1670.0".to_string(), "45.2".to_string()],
+            vec!["1670.5".to_string()],
+        ],
+        comments: None,
+        header: "~A".to_string(),
+    });
+    assert!(data.curves.iter().all(|c| c.len() == 2));
+    assert_eq!(data.row(1).unwrap(), ["1670.5", ""]);
+
+    // Curves are pub, so they can still end up different lengths.
+    data.curves[1] = sections::Curve::new("GR");
+    assert_eq!(data.rows().collect::<Vec<_>>(), [["1670.0", ""], ["1670.5", ""]]);
+    assert!(data.to_string().contains("1670.5"));
+}
+
+#[test]
+fn test_error_display() {
+    let e = ParseError::AsciiColumnsMismatch {
+        line_number: 12,
+        num_cols_in_headers: 8,
+        num_cols_in_row: 7,
+    };
+    assert_eq!(e.to_string(), "line 12: row has 7 values, expected 8");
+    // Float error is rounded away.
+    let e = ParseError::InconsistentDepthStep {
+        step: -0.125,
+        found: 1669.875 - 1669.99,
+        depth: 1669.875,
+    };
+    assert_eq!(
+        e.to_string(),
+        "depth 1669.875 is -0.115 from the one before it, STEP is -0.125"
+    );
+}
+
+#[test]
+fn test_convert_batch() {
+    let dir = TestDir::new("convert_batch");
+    let out_dir = dir.join("out");
+    let options = BatchOptions {
+        output_format: OutputFormat::JSON,
+        out_dir: out_dir.clone(),
+        workers: Some(4),
+        overwrite: false,
+    };
+    // The directory and the glob overlap, every file is still only converted once.
+    let report = convert_batch(&["las_files", "las_files/*.LAS"], &options).unwrap();
+    let num_files = std::fs::read_dir("las_files").unwrap().count();
+    assert_eq!(report.files.len(), num_files);
+    assert_eq!(report.succeeded + report.failed, num_files);
+    assert!(report.files.windows(2).all(|w| w[0].input < w[1].input));
+
+    let good = report
+        .files
+        .iter()
+        .find(|f| f.input.ends_with("_good_sample_1.las"))
+        .unwrap();
+    assert!(good.error.is_none());
+    assert_eq!(good.output, out_dir.join("_good_sample_1.json"));
+    assert!(LasFile::try_from_json_str(&std::fs::read_to_string(&good.output).unwrap()).is_ok());
+
+    // A bad file is reported with its error, and nothing is left in the output tree.
+    let bad = report
+        .files
+        .iter()
+        .find(|f| f.input.ends_with("version_info_not_first.las"))
+        .unwrap();
+    assert_eq!(
+        bad.error.as_deref(),
+        Some("line 1: the Version section has to be the first section")
+    );
+    assert!(!bad.output.exists());
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json_str().unwrap()).unwrap();
+    assert_eq!(json["failed"].as_u64(), Some(report.failed as u64));
+
+    // Without `overwrite` existing output is left alone and reported.
+    let again = convert_batch(&["las_files/_good_sample_1.las"], &options).unwrap();
+    assert_eq!(again.failed, 1);
+}
+
+#[test]
+fn test_convert_batch_into_input_dir() {
+    let dir = TestDir::new("convert_batch_into_input_dir");
+    let original = std::fs::read("las_files/_good_sample_1.las").unwrap();
+    std::fs::write(dir.join("a.las"), &original).unwrap();
+
+    // Written as "./" so the paths aren't equal as text.
+    let options = BatchOptions {
+        output_format: OutputFormat::LAS,
+        out_dir: dir.join("."),
+        workers: None,
+        overwrite: true,
+    };
+    let report = convert_batch(&[dir.path().to_str().unwrap()], &options).unwrap();
+    assert_eq!(report.failed, 1);
+    assert_eq!(std::fs::read(dir.join("a.las")).unwrap(), original);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+    // Other formats are written next to the input.
+    let options = BatchOptions {
+        output_format: OutputFormat::JSON,
+        ..options
+    };
+    let report = convert_batch(&[dir.path().to_str().unwrap()], &options).unwrap();
+    assert_eq!(report.succeeded, 1);
+    assert!(dir.join("a.json").exists());
+    assert_eq!(std::fs::read(dir.join("a.las")).unwrap(), original);
+}
+
+#[test]
+fn test_convert_batch_duplicate_outputs() {
+    let dir = TestDir::new("convert_batch_duplicate_outputs");
+    for run in ["a", "b"] {
+        std::fs::create_dir_all(dir.join(run)).unwrap();
+        std::fs::copy("las_files/_good_sample_1.las", dir.join(run).join("x.las")).unwrap();
+    }
+
+    // "a/x.las" and "b/x.las" are both "x.json" within their roots.
+    let options = BatchOptions {
+        output_format: OutputFormat::JSON,
+        out_dir: dir.join("out"),
+        workers: Some(2),
+        overwrite: false,
+    };
+    let inputs = [dir.join("a"), dir.join("b")];
+    let inputs: Vec<&str> = inputs.iter().map(|p| p.to_str().unwrap()).collect();
+    let report = convert_batch(&inputs, &options).unwrap();
+    assert_eq!((report.succeeded, report.failed), (1, 1));
+    assert!(report.files[0].error.is_none());
+    assert_eq!(report.files[1].output, report.files[0].output);
+    let error = report.files[1].error.as_deref().unwrap();
+    assert!(error.contains("is already the output of"), "{error}");
+    assert!(
+        error.contains(&dir.join("a").join("x.las").display().to_string()),
+        "{error}"
+    );
+}
+
+#[test]
+fn test_windows_1252_encoding() {
+    let file_path = "las_files/windows_1252.las";
+    let las_file = parse(file_path).unwrap();
+    assert_eq!(las_file.encoding, Encoding::Windows1252);
+    let bht = &las_file.parameter_information.as_ref().unwrap().parameters[1];
+    assert_eq!(bht.description.as_deref(), Some("BOTTOM HOLE TEMPERATURE (°C)"));
+
+    // Written back as it was read, every other byte is left alone.
+    let mut writer = LasWriter::new(vec![], LasWriterOptions::default());
+    writer.write(&las_file).unwrap();
+    let bytes = writer.into_inner().unwrap();
+    assert!(bytes.windows(4).any(|w| w == b"(\xB0C)"));
+    assert!(std::str::from_utf8(&bytes).is_err());
+    let reparsed = parse_from_with_options(bytes.as_slice(), ParseOptions::default())
+        .unwrap()
+        .0;
+    assert_eq!(las_file, reparsed);
+
+    // Or in another encoding, as long as it has every character.
+    let utf8 = LasWriterOptions {
+        encoding: Encoding::Utf8,
+        ..Default::default()
+    };
+    let mut writer = LasWriter::new(vec![], utf8);
+    writer.write(&las_file).unwrap();
+    assert!(
+        String::from_utf8(writer.into_inner().unwrap())
+            .unwrap()
+            .contains("(µs/m)")
+    );
+    let latin1 = LasWriterOptions {
+        encoding: Encoding::Latin1,
+        ..Default::default()
+    };
+    let mut writer = LasWriter::new(vec![], latin1);
+    assert!(matches!(writer.write(&las_file), Err(ParseError::Io(e)) if e.kind() == std::io::ErrorKind::InvalidData));
+
+    let options = ParseOptions {
+        encoding: Encoding::Utf8,
+        ..Default::default()
+    };
+    match parse_with_options(file_path, options) {
+        Err(ParseError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidData),
+        other => panic!("Expected an InvalidData error but got {other:?}"),
+    }
+    let options = ParseOptions {
+        encoding: Encoding::Latin1,
+        ..Default::default()
+    };
+    let (latin1_file, _) = parse_with_options(file_path, options).unwrap();
+    assert_eq!(latin1_file.encoding, Encoding::Latin1);
+    assert_eq!(
+        latin1_file
+            .well_information
+            .comp
+            .as_ref()
+            .unwrap()
+            .value
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "ANY OIL COMPANY INC. \u{96} NORD"
+    );
+}
+
+#[test]
+fn test_utf8_bom() {
+    let text = std::fs::read_to_string("las_files/_good_sample_1.las").unwrap();
+    let with_bom = format!("\u{FEFF}{text}");
+    let (mut las_file, diagnostics) = parse_from_with_options(with_bom.as_bytes(), ParseOptions::default()).unwrap();
+    assert!(diagnostics.is_empty());
+    assert_eq!(las_file.encoding, Encoding::Utf8Bom);
+
+    // Written back with the byte order mark, by both writers.
+    let mut writer = LasWriter::new(vec![], LasWriterOptions::default());
+    writer.write(&las_file).unwrap();
+    assert!(writer.into_inner().unwrap().starts_with(b"\xEF\xBB\xBF"));
+    let doc = LasDocument::from_reader(with_bom.as_bytes()).unwrap();
+    let mut bytes = vec![];
+    doc.write_to(&mut bytes).unwrap();
+    assert_eq!(bytes, with_bom.as_bytes());
+
+    las_file.encoding = Encoding::Auto;
+    assert_eq!(las_file, parse("las_files/_good_sample_1.las").unwrap());
+}
+
+#[test]
+fn test_las_document_windows_1252() {
+    let file_path = "las_files/windows_1252.las";
+    let original = std::fs::read(file_path).unwrap();
+    let mut doc = LasDocument::open(file_path).unwrap();
+    assert_eq!(doc.encoding(), Encoding::Windows1252);
+    assert_eq!(doc.to_las_file().unwrap(), parse(file_path).unwrap());
+
+    // Only the edited line changes, everything else is written back in Windows-1252.
+    doc.set_value(SectionKind::Well, "STOP", "1669.7000").unwrap();
+    let mut bytes = vec![];
+    doc.write_to(&mut bytes).unwrap();
+    let lines: Vec<&[u8]> = bytes.split(|&b| b == b'\n').collect();
+    let original_lines: Vec<&[u8]> = original.split(|&b| b == b'\n').collect();
+    let changed: Vec<usize> = (0..lines.len()).filter(|&i| lines[i] != original_lines[i]).collect();
+    assert_eq!(changed, [9]);
+    assert!(bytes.windows(4).any(|w| w == b"(\xB0C)"));
+
+    // Characters Windows-1252 doesn't have can't be written.
+    doc.set_value(SectionKind::Well, "COMP", "\u{263A}").unwrap();
+    match doc.write_to(vec![]) {
+        Err(ParseError::Io(e)) => assert_eq!(e.kind(), std::io::ErrorKind::InvalidData),
+        other => panic!("Expected an InvalidData error but got {other:?}"),
+    }
+}
+
+#[test]
+fn test_parse_header() {
+    let file_path = "las_files/_good_sample_1.las";
+    let las_file = parse(file_path).unwrap();
+    let header = parse_header(file_path).unwrap();
+    assert_eq!(
+        serde_json::to_value(&header.well_information).unwrap(),
+        serde_json::to_value(&las_file.well_information).unwrap()
+    );
+    assert_eq!(header.curve_information.curves.len(), 8);
+    assert!(header.parameter_information.is_some());
+
+    // Rows start on the line after "~A".
+    let text = std::fs::read_to_string(file_path).unwrap();
+    let data_offset = header.data_offset.unwrap() as usize;
+    assert!(text[..data_offset].lines().last().unwrap().starts_with("~A"));
+    let first_row = las_file.ascii_log_data.row(0).unwrap();
+    assert!(text[data_offset..].trim_start().starts_with(first_row[0]));
+
+    let num_rows = header.count_rows(File::open(file_path).unwrap()).unwrap();
+    assert_eq!(num_rows, Some(las_file.ascii_log_data.num_rows()));
+    let last_index = header.last_index(File::open(file_path).unwrap()).unwrap();
+    assert_eq!(last_index, las_file.index().unwrap().values().last().copied());
+}
+
+#[test]
+fn test_parse_header_skips_log_data() {
+    // Only the ASCII log data of this file is broken.
+    let file_path = "las_files/ascii_data_row_incorrect_length.las";
+    assert!(parse(file_path).is_err());
+    let header = parse_header(file_path).unwrap();
+    assert!(header.data_offset.is_some());
+
+    let wrapped = parse_header("las_files/wrapped.las").unwrap();
+    assert_eq!(
+        wrapped
+            .count_rows(File::open("las_files/wrapped.las").unwrap())
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        wrapped
+            .last_index(File::open("las_files/wrapped.las").unwrap())
+            .unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_indexed_las_reader() {
+    // Header of a sample file followed by 5000 rows, 0.5 apart.
+    let mut text = sample_header();
+    for i in 0..5000 {
+        let depth = 1000.0 + i as f64 * 0.5;
+        text.push_str(&format!("{depth:.3} {i} 2.5 0.3 -999.25 10.0 11.0 12.0\n"));
+    }
+    let dir = TestDir::new("indexed_las_reader");
+    let las_path = dir.join("rows.las");
+    let index_path = dir.join("rows.las.idx");
+    std::fs::write(&las_path, &text).unwrap();
+    let las_path = las_path.to_str().unwrap();
+
+    let options = IndexOptions {
+        interval: 64,
+        index_path: Some(index_path.clone()),
+    };
+    let reader = IndexedLasReader::open_with_options(las_path, options.clone()).unwrap();
+    assert_eq!(reader.num_rows(), 5000);
+    assert_eq!(reader.index().entries.len(), 5000usize.div_ceil(64));
+
+    let mut expected = parse(las_path).unwrap();
+    expected.slice_depth(1300.0, 1200.25).unwrap();
+    let found = reader.read_range(1300.0, 1200.25).unwrap();
+    assert_eq!(found.ascii_log_data, expected.ascii_log_data);
+    assert_eq!(found.ascii_log_data.num_rows(), 200);
+    assert_eq!(
+        found.well_information.strt.value.as_ref().unwrap().to_string(),
+        "1200.500"
+    );
+
+    assert_eq!(reader.row(4999).unwrap().unwrap()[..2], ["3499.500", "4999"]);
+    assert_eq!(reader.row(5000).unwrap(), None);
+
+    // The persisted index is loaded instead of scanning the file again.
+    assert!(index_path.exists());
+    let reopened = IndexedLasReader::open_with_options(las_path, options).unwrap();
+    assert_eq!(reopened.index(), reader.index());
+    let _ = std::fs::remove_file(&index_path);
+
+    // Not being able to persist the index doesn't stop us reading the file.
+    let options = IndexOptions {
+        interval: 64,
+        index_path: Some(dir.join("missing_dir").join("index.idx")),
+    };
+    let reader = IndexedLasReader::open_with_options(las_path, options).unwrap();
+    assert_eq!(reader.num_rows(), 5000);
+
+    // Every depth is checked, not only the indexed ones.
+    let text = text.replacen("1200.500 ", "9999.000 ", 1);
+    std::fs::write(las_path, &text).unwrap();
+    assert!(matches!(
+        IndexedLasReader::open_with_options(las_path, IndexOptions::default()),
+        Err(ParseError::NonMonotonicIndex { depth, .. }) if depth < 9999.0
+    ));
+}
+
+#[test]
+fn test_indexed_las_reader_small_files() {
+    // Decreasing depths.
+    let options = IndexOptions {
+        interval: 1,
+        index_path: None,
+    };
+    let reader = IndexedLasReader::open_with_options("las_files/_good_sample_1.las", options).unwrap();
+    let mut expected = parse("las_files/_good_sample_1.las").unwrap();
+    expected.slice_depth(1669.8, 1670.0).unwrap();
+    assert_eq!(
+        reader.read_range(1669.8, 1670.0).unwrap().ascii_log_data,
+        expected.ascii_log_data
+    );
+
+    // Rows wrapped over several lines.
+    let options = IndexOptions {
+        interval: 2,
+        index_path: None,
+    };
+    let reader = IndexedLasReader::open_with_options("las_files/wrapped.las", options).unwrap();
+    let las_file = parse("las_files/wrapped.las").unwrap();
+    assert_eq!(reader.num_rows(), las_file.ascii_log_data.num_rows());
+    for (n, row) in las_file.ascii_log_data.rows().enumerate() {
+        assert_eq!(reader.row(n).unwrap().unwrap(), row);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parse_parallel() {
+    for file_path in [
+        "las_files/_good_sample_1.las",
+        "las_files/las3_sample.las",
+        "las_files/wrapped.las",
+        "las_files/comment_after_block.las",
+    ] {
+        let expected = parse(file_path).unwrap();
+        // A chunk every few lines, so rows end up in several chunks.
+        let found = parallel::parse_chunked(file_path, 64).unwrap();
+        assert_eq!(found.num_rows(), expected.ascii_log_data.num_rows(), "{file_path}");
+        assert_eq!(found.columns.len(), expected.ascii_log_data.curves.len(), "{file_path}");
+        for (column, curve) in found.columns.iter().zip(&expected.ascii_log_data.curves) {
+            // Bits, so NaN equals NaN.
+            let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+            assert_eq!(bits(column), bits(curve.values()), "{file_path} {}", curve.mnemonic);
+        }
+        assert_eq!(found.header.well_information, expected.well_information);
+    }
+
+    let las_file = parse_parallel("las_files/_good_sample_1.las").unwrap();
+    assert_eq!(las_file.column("dept").unwrap()[0], 1670.0);
+    assert_eq!(las_file.null_value(), Some(-999.25));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parse_parallel_line_numbers() {
+    // Comments and blank lines aren't allowed in ASCII log data either.
+    let sample = std::fs::read_to_string("las_files/_good_sample_1.las").unwrap();
+    let dir = TestDir::new("parse_parallel_line_numbers");
+    let trailing_comment = dir.join("comment.las");
+    std::fs::write(&trailing_comment, format!("{sample}# trailing comment\n")).unwrap();
+    let blank_line = dir.join("blank.las");
+    std::fs::write(&blank_line, sample.replacen("\n1669.875", "\n\n1669.875", 1)).unwrap();
+
+    for file_path in [
+        "las_files/ascii_data_row_incorrect_length.las",
+        trailing_comment.to_str().unwrap(),
+        blank_line.to_str().unwrap(),
+    ] {
+        let expected = parse(file_path).unwrap_err().to_string();
+        for chunk_size in [1, 16, 1024 * 1024] {
+            match parallel::parse_chunked(file_path, chunk_size) {
+                Err(e) => assert_eq!(e.to_string(), expected, "{file_path}"),
+                Ok(_) => panic!("Expected {expected} but got Ok"),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_typed_curves() {
+    let file_path = "las_files/_good_sample_1.las";
+    let las_file = parse(file_path).unwrap();
+
+    let index = las_file.index().unwrap();
+    assert_eq!(index.mnemonic, "DEPT");
+    assert_eq!(index.values(), [1670.0, 1669.875, 1669.75]);
+
+    let ild = las_file.curve("ild").unwrap();
+    assert_eq!(ild.get(2), Some(105.6));
+    assert_eq!(ild.raw()[0], "05.600");
+    assert!(las_file.curve("GR").is_none());
+}
+
+#[test]
+fn test_typed_curves_null_values() {
+    let file_path = "las_files/wrapped.las";
+    let las_file = parse(file_path).unwrap();
+    let null = las_file.well_information.null_value().unwrap();
+    assert!(las_file.curve("DT").unwrap().iter().all(|v| v.is_none()));
+
+    for curve in las_file.ascii_log_data.curves.iter() {
+        assert_eq!(curve.null_value(), Some(null));
+        for (i, value) in curve.iter().enumerate() {
+            assert_eq!(value.is_none(), curve.values()[i] == null);
+        }
+    }
+}
+
 #[test]
 fn test_json_deserialization() {
     let file_path = "las_files/_good_sample_1.las";
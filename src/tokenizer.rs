@@ -1,3 +1,4 @@
+use crate::Encoding;
 use std::io::{self, BufRead};
 
 pub struct LasTokenizer<R>
@@ -5,8 +6,12 @@ where
     R: BufRead,
 {
     reader: R,
+    // Lines are read as bytes and decoded, so one byte that isn't UTF-8 doesn't fail the whole file.
+    bytes: Vec<u8>,
     buffer: String,
     line: usize,
+    bytes_read: u64,
+    encoding: Encoding,
 }
 
 impl<R> LasTokenizer<R>
@@ -14,22 +19,43 @@ where
     R: BufRead,
 {
     pub fn new(reader: R) -> Self {
+        Self::with_encoding(reader, Encoding::Auto)
+    }
+
+    pub fn with_encoding(reader: R, encoding: Encoding) -> Self {
         Self {
             reader,
+            bytes: vec![],
             buffer: String::new(),
             line: 0,
+            bytes_read: 0,
+            encoding,
         }
     }
 
+    /// Encoding of what has been read so far. [`Encoding::Auto`] stays as is while lines are valid UTF-8,
+    /// and becomes Windows-1252 at the first line that isn't. A file read as UTF-8 that starts with a byte
+    /// order mark is [`Encoding::Utf8Bom`].
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Bytes read so far, which is where the next line starts.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
     pub fn next_token(&mut self) -> io::Result<Option<LasToken>> {
-        self.buffer.clear();
+        self.bytes.clear();
 
-        let bytes = self.reader.read_line(&mut self.buffer)?;
+        let bytes = self.reader.read_until(b'\n', &mut self.bytes)?;
         if bytes == 0 {
             return Ok(None); // EOF
         }
 
         self.line += 1;
+        self.bytes_read += bytes as u64;
+        self.decode_line()?;
         let line = self.buffer.trim_end_matches(&['\n', '\r'][..]);
         let trimmed = line.trim_start();
 
@@ -56,6 +82,23 @@ where
             line_number: self.line,
         }))
     }
+
+    // Decodes `bytes` into `buffer`.
+    fn decode_line(&mut self) -> io::Result<()> {
+        let mut bytes = self.bytes.as_slice();
+        if self.line == 1 {
+            bytes = self.encoding.strip_bom(bytes);
+        }
+        let Some(text) = self.encoding.decode_or_fallback(bytes) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {} isn't valid {}", self.line, self.encoding),
+            ));
+        };
+        self.buffer.clear();
+        self.buffer.push_str(&text);
+        Ok(())
+    }
 }
 
 impl<R> Iterator for LasTokenizer<R>
@@ -0,0 +1,233 @@
+use crate::{DataLine, LasFile, LasValue, ParseError, decimal_places, parse::missing_curve, sections::Curve};
+
+// Converted values keep at least this many decimals, so "390" metres isn't rounded to "1280" feet.
+const MIN_CONVERTED_DECIMALS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Time,
+    Density,
+    /// Sonic transit time, eg. "US/M"
+    Slowness,
+    Temperature,
+    /// Porosity, saturation and other volume fractions, eg. "V/V" or "PU"
+    Fraction,
+    Pressure,
+    Resistivity,
+}
+
+/// A unit we know how to convert, along with the ways it is commonly written in LAS files.
+#[derive(Debug)]
+pub struct Unit {
+    /// How we write the unit, eg. "FT"
+    pub symbol: &'static str,
+    pub dimension: Dimension,
+    /// Other ways of writing the unit, eg. "F" or "FEET". Matched case insensitively, like `symbol`.
+    pub aliases: &'static [&'static str],
+    // value * scale + offset gives the value in the base unit of `dimension`.
+    scale: f64,
+    offset: f64,
+}
+
+const fn unit(
+    symbol: &'static str,
+    dimension: Dimension,
+    aliases: &'static [&'static str],
+    scale: f64,
+    offset: f64,
+) -> Unit {
+    Unit {
+        symbol,
+        dimension,
+        aliases,
+        scale,
+        offset,
+    }
+}
+
+/// Every unit we know. "F" is feet (as in "STRT.F"), Fahrenheit is "DEGF".
+pub const UNITS: &[Unit] = &[
+    // Base unit : metres
+    unit(
+        "M",
+        Dimension::Length,
+        &["METER", "METERS", "METRE", "METRES"],
+        1.0,
+        0.0,
+    ),
+    unit("FT", Dimension::Length, &["F", "FEET", "FOOT"], 0.3048, 0.0),
+    unit("CM", Dimension::Length, &[], 0.01, 0.0),
+    unit("MM", Dimension::Length, &[], 0.001, 0.0),
+    unit("IN", Dimension::Length, &["INCH", "INCHES"], 0.0254, 0.0),
+    // Base unit : seconds
+    unit("S", Dimension::Time, &["SEC", "SECS"], 1.0, 0.0),
+    unit("MS", Dimension::Time, &["MSEC"], 1e-3, 0.0),
+    unit("US", Dimension::Time, &["USEC"], 1e-6, 0.0),
+    unit("MIN", Dimension::Time, &[], 60.0, 0.0),
+    unit("HR", Dimension::Time, &["H"], 3600.0, 0.0),
+    // Base unit : kilograms per cubic metre
+    unit("K/M3", Dimension::Density, &["KG/M3"], 1.0, 0.0),
+    unit("G/C3", Dimension::Density, &["G/CC", "G/CM3", "GM/CC"], 1000.0, 0.0),
+    // Base unit : seconds per metre
+    unit("US/M", Dimension::Slowness, &[], 1e-6, 0.0),
+    unit("US/F", Dimension::Slowness, &["US/FT"], 1e-6 / 0.3048, 0.0),
+    // Base unit : degrees Celsius
+    unit("DEGC", Dimension::Temperature, &["C", "DEG C", "DEG_C"], 1.0, 0.0),
+    unit(
+        "DEGF",
+        Dimension::Temperature,
+        &["DEG F", "DEG_F"],
+        5.0 / 9.0,
+        -32.0 * 5.0 / 9.0,
+    ),
+    unit("DEGK", Dimension::Temperature, &["K"], 1.0, -273.15),
+    // Base unit : volume per volume
+    unit(
+        "V/V",
+        Dimension::Fraction,
+        &["DEC", "FRAC", "M3/M3", "FT3/FT3"],
+        1.0,
+        0.0,
+    ),
+    unit("%", Dimension::Fraction, &["PU", "PERCENT"], 0.01, 0.0),
+    // Base unit : pascals
+    unit("PA", Dimension::Pressure, &[], 1.0, 0.0),
+    unit("KPA", Dimension::Pressure, &[], 1e3, 0.0),
+    unit("MPA", Dimension::Pressure, &[], 1e6, 0.0),
+    unit("BAR", Dimension::Pressure, &[], 1e5, 0.0),
+    unit("PSI", Dimension::Pressure, &[], 6894.757293168, 0.0),
+    // Base unit : ohm metres
+    unit("OHMM", Dimension::Resistivity, &["OHM.M", "OHM-M"], 1.0, 0.0),
+];
+
+impl Unit {
+    /// Looks up a unit by symbol or alias, case insensitive, eg. "g/cc" -> "G/C3".
+    pub fn find(unit: &str) -> Option<&'static Unit> {
+        let unit = unit.trim();
+        UNITS
+            .iter()
+            .find(|u| u.symbol.eq_ignore_ascii_case(unit) || u.aliases.iter().any(|a| a.eq_ignore_ascii_case(unit)))
+    }
+
+    /// Converts `value` in this unit to `to`, eg. metres to feet.
+    pub fn convert(&self, value: f64, to: &Unit) -> Result<f64, ParseError> {
+        if self.dimension != to.dimension {
+            return Err(ParseError::IncompatibleUnits {
+                from: self.symbol.to_string(),
+                to: to.symbol.to_string(),
+            });
+        }
+        Ok((value * self.scale + self.offset - to.offset) / to.scale)
+    }
+
+    // Same as `convert`, for the difference between two values (eg. "STEP"), which isn't offset.
+    fn convert_difference(&self, value: f64, to: &Unit) -> f64 {
+        value * self.scale / to.scale
+    }
+}
+
+impl LasFile {
+    /// Converts a curve, and every value in its column of ASCII log data, to the unit `to`, eg.
+    /// `convert_units("RHOB", "g/cc")`. Units are looked up with [`Unit::find`] and written as
+    /// [`Unit::symbol`]. NULL values are left as is.
+    ///
+    /// Converting the index curve (see [`LasFile::convert_depth_units`]) also converts "STRT", "STOP",
+    /// "STEP" and any other Well or Parameter line in the same unit as the index, eg. elevations.
+    pub fn convert_units(&mut self, mnemonic: &str, to: &str) -> Result<(), ParseError> {
+        let position = self
+            .curve_information
+            .curves
+            .iter()
+            .position(|c| c.mnemonic.eq_ignore_ascii_case(mnemonic))
+            .ok_or_else(|| missing_curve(mnemonic))?;
+
+        let from_text = self.curve_information.curves[position].unit.clone().unwrap_or_default();
+        let from = find_unit(&from_text)?;
+        let to_unit = find_unit(to)?;
+        // Check before changing anything.
+        from.convert(0.0, to_unit)?;
+
+        if let Some(curve) = self.ascii_log_data.curves.get_mut(position) {
+            convert_curve(curve, from, to_unit);
+        }
+
+        if position == 0 {
+            let lines = self.well_information.data_lines_mut().into_iter().chain(
+                self.parameter_information
+                    .iter_mut()
+                    .flat_map(|p| p.parameters.iter_mut()),
+            );
+            for line in lines {
+                if !line.unit.as_deref().is_some_and(|u| u.eq_ignore_ascii_case(&from_text)) {
+                    continue;
+                }
+                convert_data_line(line, from, to_unit);
+            }
+        }
+
+        self.curve_information.curves[position].unit = Some(to_unit.symbol.to_string());
+        Ok(())
+    }
+
+    /// Converts the index curve (eg. "DEPT") to `to`, eg. `convert_depth_units("FT")`.
+    /// See [`LasFile::convert_units`].
+    pub fn convert_depth_units(&mut self, to: &str) -> Result<(), ParseError> {
+        let index = self
+            .curve_information
+            .curves
+            .first()
+            .map(|c| c.mnemonic.clone())
+            .ok_or_else(|| ParseError::Error {
+                message: "There are no curves!".to_string(),
+            })?;
+        self.convert_units(&index, to)
+    }
+}
+
+fn find_unit(unit: &str) -> Result<&'static Unit, ParseError> {
+    Unit::find(unit).ok_or_else(|| ParseError::UnknownUnit { unit: unit.to_string() })
+}
+
+fn convert_curve(curve: &mut Curve, from: &Unit, to: &Unit) {
+    for i in 0..curve.len() {
+        // NULL and text values are left as is.
+        let Some(value) = curve.get(i) else {
+            continue;
+        };
+        // Can't fail, the dimensions were checked by the caller.
+        let Ok(converted) = from.convert(value, to) else {
+            continue;
+        };
+        let decimals = converted_decimals(&curve.raw()[i], from, to);
+        curve.set(i, format!("{converted:.decimals$}"));
+    }
+}
+
+fn convert_data_line(line: &mut DataLine, from: &Unit, to: &Unit) {
+    // Text values, eg. "UNKNOWN", are left as is.
+    if let Some(value) = line.value.as_ref()
+        && let Some(number) = value.as_f64()
+    {
+        let converted = match line.mnemonic.eq_ignore_ascii_case("STEP") {
+            true => Ok(from.convert_difference(number, to)),
+            false => from.convert(number, to),
+        };
+        if let Ok(converted) = converted {
+            let decimals = converted_decimals(&value.to_string(), from, to);
+            line.value = LasValue::new(&format!("{converted:.decimals$}"));
+        }
+    }
+    line.unit = Some(to.symbol.to_string());
+}
+
+// Keeps the precision of the original text, with extra decimals when values shrink, eg. "2550.000" K/M3 -> "2.550000" G/C3.
+fn converted_decimals(raw: &str, from: &Unit, to: &Unit) -> usize {
+    let ratio = from.scale / to.scale;
+    let extra = if ratio < 1.0 {
+        (-ratio.log10()).ceil() as usize
+    } else {
+        0
+    };
+    (decimal_places(raw) + extra).max(MIN_CONVERTED_DECIMALS)
+}
@@ -0,0 +1,348 @@
+use crate::{
+    DEFAULT_WRAP_WIDTH, DataLine, Delimiter, Encoding, LasFile, ParseError,
+    sections::{AsciiLogData, CurveInformation, ParameterInformation, write_wrapped_row},
+    write_comments,
+};
+use std::{
+    fmt,
+    io::{self, BufWriter, Write},
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    LF,
+    CRLF,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct LasWriterOptions {
+    /// Line up the mnemonic, unit, value and description of data lines within each section.
+    pub align_data_lines: bool,
+    /// Right align each column of ASCII log data to the width of its widest value.
+    /// Has no effect on wrapped ("WRAP. YES") data.
+    pub align_columns: bool,
+    /// Rewrite numeric ASCII log data values with this many decimal places. Text values are left as is.
+    pub decimals: Option<usize>,
+    pub line_ending: LineEnding,
+    /// Write section headers in upper case, eg. "~WELL INFORMATION".
+    pub uppercase_headers: bool,
+    /// [`Encoding::Auto`] writes a file back in the encoding it was read in (see [`LasFile::encoding`]),
+    /// and UTF-8 when there isn't one.
+    pub encoding: Encoding,
+}
+
+impl LasWriterOptions {
+    /// Aligned data lines and columns, the way most logging software writes LAS files.
+    pub fn pretty() -> Self {
+        Self {
+            align_data_lines: true,
+            align_columns: true,
+            ..Default::default()
+        }
+    }
+}
+
+/// Writes a [`LasFile`] to any [`io::Write`], line by line.
+/// We wrap your writer in [`BufWriter`].
+pub struct LasWriter<W>
+where
+    W: Write,
+{
+    writer: BufWriter<W>,
+    options: LasWriterOptions,
+}
+
+impl<W> LasWriter<W>
+where
+    W: Write,
+{
+    pub fn new(writer: W, options: LasWriterOptions) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+            options,
+        }
+    }
+
+    pub fn write(&mut self, las_file: &LasFile) -> Result<(), ParseError> {
+        self.write_with(las_file.encoding, |out, options| write_las(out, las_file, options))
+    }
+
+    // Runs `write` on top of the underlying writer, then flushes it. `source_encoding` is the encoding
+    // of what is being written, used unless the options ask for another one.
+    pub(crate) fn write_with<F>(&mut self, source_encoding: Encoding, write: F) -> Result<(), ParseError>
+    where
+        F: FnOnce(&mut IoWriter<'_, BufWriter<W>>, &LasWriterOptions) -> fmt::Result,
+    {
+        let encoding = match self.options.encoding {
+            Encoding::Auto => source_encoding,
+            encoding => encoding,
+        };
+        self.writer.write_all(encoding.bom())?;
+        let mut out = IoWriter {
+            writer: &mut self.writer,
+            line_ending: self.options.line_ending,
+            encoding,
+            error: None,
+        };
+        if write(&mut out, &self.options).is_err() {
+            return Err(match out.error.take() {
+                Some(e) => ParseError::Io(e),
+                None => ParseError::ConvertingTo {
+                    format: "las".to_string(),
+                },
+            });
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> Result<W, ParseError> {
+        self.writer.into_inner().map_err(|e| ParseError::Io(e.into_error()))
+    }
+}
+
+// Lets us use `write!` on top of an `io::Write`, converting line endings and encoding on the way.
+pub(crate) struct IoWriter<'a, W>
+where
+    W: Write,
+{
+    writer: &'a mut W,
+    line_ending: LineEnding,
+    encoding: Encoding,
+    // `fmt::Error` carries nothing, so we hold onto the real error here.
+    error: Option<io::Error>,
+}
+
+impl<W> fmt::Write for IoWriter<'_, W>
+where
+    W: Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let bytes = match self.encoding.try_encode(s) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                self.error = Some(e);
+                return Err(fmt::Error);
+            }
+        };
+        let result = match self.line_ending {
+            LineEnding::LF => self.writer.write_all(&bytes),
+            LineEnding::CRLF => bytes.split(|&b| b == b'\n').enumerate().try_for_each(|(i, part)| {
+                if i > 0 {
+                    self.writer.write_all(b"\r\n")?;
+                }
+                self.writer.write_all(part)
+            }),
+        };
+        result.map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+pub(crate) fn write_las(out: &mut impl fmt::Write, las_file: &LasFile, options: &LasWriterOptions) -> fmt::Result {
+    write_sections_before_log_data(out, las_file, options)?;
+
+    let version = &las_file.version_information;
+    // LAS 3.0 files don't have to contain log data.
+    if !las_file.ascii_log_data.header.is_empty() {
+        let wrap_width = version.is_wrapped().then_some(DEFAULT_WRAP_WIDTH);
+        write_log_data(out, &las_file.ascii_log_data, version.delimiter(), wrap_width, options)?;
+    }
+
+    write_section_groups(out, las_file, options)
+}
+
+// Every section that comes before "~A", from "~V" to "~O".
+pub(crate) fn write_sections_before_log_data(
+    out: &mut impl fmt::Write,
+    las_file: &LasFile,
+    options: &LasWriterOptions,
+) -> fmt::Result {
+    let version = &las_file.version_information;
+    write_comments(out, &version.comments)?;
+    write_header(out, &version.header, options)?;
+    write_data_lines(out, &version.data_lines(), options)?;
+
+    let well = &las_file.well_information;
+    write_comments(out, &well.comments)?;
+    write_header(out, &well.header, options)?;
+    write_data_lines(out, &well.data_lines(), options)?;
+
+    write_curves(out, &las_file.curve_information, options)?;
+    if let Some(parameter) = las_file.parameter_information.as_ref() {
+        write_parameters(out, parameter, options)?;
+    }
+
+    if let Some(other) = las_file.other_information.as_ref() {
+        write_comments(out, &other.comments)?;
+        write_header(out, &other.header, options)?;
+        for data in other.data.iter() {
+            write!(out, "{data}")?;
+        }
+    }
+    Ok(())
+}
+
+// LAS 3.0 section groups other than "Log", which come after "~A".
+pub(crate) fn write_section_groups(
+    out: &mut impl fmt::Write,
+    las_file: &LasFile,
+    options: &LasWriterOptions,
+) -> fmt::Result {
+    let delimiter = las_file.version_information.delimiter();
+    for group in las_file.section_groups.iter() {
+        if let Some(parameter) = group.parameter.as_ref() {
+            write_parameters(out, parameter, options)?;
+        }
+        if let Some(definition) = group.definition.as_ref() {
+            write_curves(out, definition, options)?;
+        }
+        for data in group.data.iter() {
+            write_log_data(out, data, delimiter, None, options)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_curves(out: &mut impl fmt::Write, curves: &CurveInformation, options: &LasWriterOptions) -> fmt::Result {
+    write_comments(out, &curves.comments)?;
+    write_header(out, &curves.header, options)?;
+    write_data_lines(out, &curves.curves.iter().collect::<Vec<_>>(), options)
+}
+
+fn write_parameters(
+    out: &mut impl fmt::Write,
+    parameters: &ParameterInformation,
+    options: &LasWriterOptions,
+) -> fmt::Result {
+    write_comments(out, &parameters.comments)?;
+    write_header(out, &parameters.header, options)?;
+    write_data_lines(out, &parameters.parameters.iter().collect::<Vec<_>>(), options)
+}
+
+pub(crate) fn write_header(out: &mut impl fmt::Write, header: &str, options: &LasWriterOptions) -> fmt::Result {
+    if options.uppercase_headers {
+        writeln!(out, "{}", header.to_uppercase())
+    } else {
+        writeln!(out, "{header}")
+    }
+}
+
+// Aligned data lines look like:
+//
+// STRT.M       1670.0000 : START DEPTH
+// NULL.         -999.25  : NULL VALUE
+// COMP.        ANY OIL   : COMPANY
+fn write_data_lines(out: &mut impl fmt::Write, lines: &[&DataLine], options: &LasWriterOptions) -> fmt::Result {
+    if !options.align_data_lines {
+        for line in lines {
+            writeln!(out, "{line}")?;
+        }
+        return Ok(());
+    }
+
+    let values: Vec<String> = lines
+        .iter()
+        .map(|line| line.value.as_ref().map(|v| v.to_string()).unwrap_or_default())
+        .collect();
+    let mnemonic_width = lines.iter().map(|l| l.mnemonic.len()).max().unwrap_or_default();
+    let unit_width = lines
+        .iter()
+        .map(|l| l.unit.as_deref().map_or(0, str::len))
+        .max()
+        .unwrap_or_default();
+    let value_width = values.iter().map(String::len).max().unwrap_or_default();
+
+    for (line, value) in lines.iter().zip(values.iter()) {
+        write_comments(out, &line.comments)?;
+        let unit = line.unit.as_deref().unwrap_or_default();
+        write!(
+            out,
+            "{:<mnemonic_width$}.{unit:<unit_width$} {value:<value_width$} :",
+            line.mnemonic
+        )?;
+        if let Some(description) = line.description.as_ref() {
+            write!(out, " {description}")?;
+        }
+        if let Some(format) = line.format.as_ref() {
+            write!(out, " {{{format}}}")?;
+        }
+        if let Some(association) = line.association.as_ref() {
+            write!(out, " | {association}")?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_log_data(
+    out: &mut impl fmt::Write,
+    data: &AsciiLogData,
+    delimiter: Delimiter,
+    wrap_width: Option<usize>,
+    options: &LasWriterOptions,
+) -> fmt::Result {
+    write_comments(out, &data.comments)?;
+    write_header(out, &data.header, options)?;
+
+    let widths: Vec<usize> = match options.align_columns && wrap_width.is_none() {
+        true => data
+            .curves
+            .iter()
+            .map(|curve| {
+                curve
+                    .raw()
+                    .iter()
+                    .map(|raw| format_cell(raw, delimiter, options).len())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect(),
+        false => vec![],
+    };
+
+    for row in data.rows() {
+        write_row(out, &row, delimiter, wrap_width, &widths, options)?;
+    }
+    Ok(())
+}
+
+// One row of ASCII log data, each cell right aligned to `widths` (if any).
+pub(crate) fn write_row<S>(
+    out: &mut impl fmt::Write,
+    row: &[S],
+    delimiter: Delimiter,
+    wrap_width: Option<usize>,
+    widths: &[usize],
+    options: &LasWriterOptions,
+) -> fmt::Result
+where
+    S: AsRef<str>,
+{
+    let cells: Vec<String> = row
+        .iter()
+        .map(|raw| format_cell(raw.as_ref(), delimiter, options))
+        .collect();
+    if let Some(line_width) = wrap_width {
+        return write_wrapped_row(out, &cells, line_width);
+    }
+    for (i, cell) in cells.iter().enumerate() {
+        if i != 0 {
+            out.write_str(delimiter.as_str())?;
+        }
+        let width = widths.get(i).copied().unwrap_or_default();
+        write!(out, "{cell:>width$}")?;
+    }
+    writeln!(out)
+}
+
+fn format_cell(raw: &str, delimiter: Delimiter, options: &LasWriterOptions) -> String {
+    let value = match (options.decimals, raw.parse::<f64>()) {
+        (Some(decimals), Ok(v)) if v.is_finite() => format!("{v:.decimals$}"),
+        _ => raw.to_string(),
+    };
+    delimiter.quote(&value)
+}